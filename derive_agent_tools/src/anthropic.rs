@@ -0,0 +1,71 @@
+//! Anthropic Messages API tool definitions and `tool_use` parsing.
+//!
+//! [`AgentTool::anthropic_tool`](crate::AgentTool::anthropic_tool) renders the
+//! `tools` entry and
+//! [`AgentTool::from_anthropic_tool_use`](crate::AgentTool::from_anthropic_tool_use)
+//! parses a `tool_use` content block back into the tool.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// A `tool_use` content block from a Messages API response.
+///
+/// The block's `"type": "tool_use"` tag is not checked, so a content block can
+/// be deserialized into this type directly once it is known to be a tool use.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolUse {
+    /// Identifier to echo back in the matching `tool_result` block.
+    pub id: String,
+    /// Name of the tool the model called.
+    pub name: String,
+    /// Tool input, matching the tool's `input_schema`.
+    pub input: Value,
+}
+
+/// Error returned when a `tool_use` block cannot be parsed into a tool.
+#[derive(Debug)]
+pub enum ToolUseError {
+    /// The block calls a different tool.
+    NameMismatch {
+        expected: &'static str,
+        found: String,
+    },
+    /// The input does not deserialize into the tool.
+    InvalidInput(serde_json::Error),
+}
+
+impl fmt::Display for ToolUseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NameMismatch { expected, found } => {
+                write!(f, "tool_use is for `{found}`, expected `{expected}`")
+            }
+            Self::InvalidInput(err) => write!(f, "invalid tool input: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for ToolUseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::NameMismatch { .. } => None,
+            Self::InvalidInput(err) => Some(err),
+        }
+    }
+}
+
+/// `{"name": ..., "description": ..., "input_schema": ...}`
+pub(crate) fn tool_definition(name: &str, description: Option<&str>, input_schema: Value) -> Value {
+    let mut map = Map::new();
+    map.insert("name".to_string(), Value::String(name.to_string()));
+    if let Some(description) = description {
+        map.insert(
+            "description".to_string(),
+            Value::String(description.to_string()),
+        );
+    }
+    map.insert("input_schema".to_string(), input_schema);
+    Value::Object(map)
+}
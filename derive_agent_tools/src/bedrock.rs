@@ -0,0 +1,308 @@
+//! AWS Bedrock Converse helpers beyond the `ToolSpecification`.
+//!
+//! [`tool_uses`] and
+//! [`AgentTool::from_tool_use`](crate::AgentTool::from_tool_use) parse the
+//! model's tool calls. With `serde-json`, [`ToolUseAccumulator`] does the same
+//! for `ConverseStream` events, and [`tool_result`] turns a handler's `Result`
+//! into the `ToolResultBlock` sent back to the model.
+
+#[cfg(feature = "serde-json")]
+use std::collections::HashMap;
+use std::fmt;
+#[cfg(feature = "serde-json")]
+use std::fmt::Display;
+
+use aws_sdk_bedrockruntime::types::ConverseOutput;
+#[cfg(feature = "serde-json")]
+use aws_sdk_bedrockruntime::types::{
+    ContentBlockDelta, ContentBlockStart, ConverseStreamOutput, ToolResultBlock,
+    ToolResultContentBlock, ToolResultStatus, ToolUseBlock,
+};
+#[cfg(feature = "serde-json")]
+use aws_smithy_types::Document;
+#[cfg(feature = "serde-json")]
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+#[cfg(feature = "serde-json")]
+use serde_json::Value;
+
+use crate::AgentTool;
+#[cfg(feature = "serde-json")]
+use crate::document::to_document;
+
+/// A parsed call to tool `T`, with the id its result must answer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolInvocation<T> {
+    /// Identifier to echo back in the matching `ToolResultBlock`.
+    pub tool_use_id: String,
+    /// The parsed tool input.
+    pub input: T,
+}
+
+#[cfg(feature = "serde-json")]
+impl<T> ToolInvocation<T> {
+    /// Builds the `ToolResultBlock` answering this call; see [`tool_result`].
+    pub fn result<O: Serialize, E: Display>(&self, result: Result<O, E>) -> ToolResultBlock {
+        tool_result(self.tool_use_id.as_str(), result)
+    }
+}
+
+/// Error returned when a `ToolUseBlock` cannot be parsed into a tool.
+#[derive(Debug)]
+pub enum ToolUseError {
+    /// The block calls a different tool.
+    NameMismatch {
+        tool_use_id: String,
+        expected: &'static str,
+        found: String,
+    },
+    /// The input does not parse into the tool.
+    InvalidInput {
+        tool_use_id: String,
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+}
+
+impl ToolUseError {
+    /// Returns the id of the failed tool use, to report the error back to the
+    /// model with [`tool_result`].
+    pub fn tool_use_id(&self) -> &str {
+        match self {
+            Self::NameMismatch { tool_use_id, .. } | Self::InvalidInput { tool_use_id, .. } => {
+                tool_use_id
+            }
+        }
+    }
+}
+
+impl fmt::Display for ToolUseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NameMismatch {
+                expected, found, ..
+            } => write!(f, "tool use is for `{found}`, expected `{expected}`"),
+            Self::InvalidInput { source, .. } => write!(f, "invalid tool input: {source}"),
+        }
+    }
+}
+
+impl std::error::Error for ToolUseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::NameMismatch { .. } => None,
+            Self::InvalidInput { source, .. } => Some(source.as_ref()),
+        }
+    }
+}
+
+/// Parses every tool use for tool `T` in a `Converse` response message.
+///
+/// Tool uses for other tools are skipped; one whose input does not parse
+/// yields an `Err` in its position, so it can still be answered with an error
+/// result.
+pub fn tool_uses<T: AgentTool + DeserializeOwned>(
+    output: &ConverseOutput,
+) -> Vec<Result<ToolInvocation<T>, ToolUseError>> {
+    let Ok(message) = output.as_message() else {
+        return Vec::new();
+    };
+    message
+        .content()
+        .iter()
+        .filter_map(|block| block.as_tool_use().ok())
+        .filter(|block| block.name() == T::tool_name())
+        .map(T::from_tool_use)
+        .collect()
+}
+
+#[cfg(feature = "serde-json")]
+/// Reassembles tool uses from `ConverseStream` events.
+///
+/// Streamed tool input arrives as JSON text fragments in
+/// `ContentBlockDelta::ToolUse` events. The accumulator buffers them per
+/// content block index and parses the input once the block's
+/// `ContentBlockStop` arrives. Events for other content blocks are ignored.
+///
+/// ```no_run
+/// # async fn run(
+/// #     mut stream: aws_sdk_bedrockruntime::primitives::event_stream::EventReceiver<
+/// #         aws_sdk_bedrockruntime::types::ConverseStreamOutput,
+/// #         aws_sdk_bedrockruntime::types::error::ConverseStreamOutputError,
+/// #     >,
+/// # ) -> Result<(), Box<dyn std::error::Error>> {
+/// use derive_agent_tools::AgentTool;
+/// use derive_agent_tools::bedrock::ToolUseAccumulator;
+/// use serde::Deserialize;
+///
+/// #[derive(AgentTool, Deserialize)]
+/// struct WeatherTool {
+///     #[tool(required)]
+///     city: String,
+/// }
+///
+/// let mut tool_uses = ToolUseAccumulator::new();
+/// while let Some(event) = stream.recv().await? {
+///     if let Some(call) = tool_uses.push_tool::<WeatherTool>(&event) {
+///         let call = call?;
+///         println!("weather for {}", call.input.city);
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct ToolUseAccumulator {
+    pending: HashMap<i32, PendingToolUse>,
+}
+
+#[cfg(feature = "serde-json")]
+#[derive(Debug)]
+struct PendingToolUse {
+    tool_use_id: String,
+    name: String,
+    input: String,
+}
+
+#[cfg(feature = "serde-json")]
+impl ToolUseAccumulator {
+    /// Creates an accumulator with no tool use in progress.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feeds one stream event, returning the tool use it completes, if any.
+    ///
+    /// A tool use whose accumulated input is not valid JSON is returned as
+    /// [`ToolUseError::InvalidInput`]; one that received no input at all gets
+    /// an empty object.
+    pub fn push(
+        &mut self,
+        event: &ConverseStreamOutput,
+    ) -> Option<Result<ToolUseBlock, ToolUseError>> {
+        match event {
+            ConverseStreamOutput::ContentBlockStart(event) => {
+                if let Some(ContentBlockStart::ToolUse(start)) = event.start() {
+                    self.pending.insert(
+                        event.content_block_index(),
+                        PendingToolUse {
+                            tool_use_id: start.tool_use_id().to_string(),
+                            name: start.name().to_string(),
+                            input: String::new(),
+                        },
+                    );
+                }
+                None
+            }
+            ConverseStreamOutput::ContentBlockDelta(event) => {
+                if let (Some(ContentBlockDelta::ToolUse(delta)), Some(pending)) = (
+                    event.delta(),
+                    self.pending.get_mut(&event.content_block_index()),
+                ) {
+                    pending.input.push_str(delta.input());
+                }
+                None
+            }
+            ConverseStreamOutput::ContentBlockStop(event) => {
+                let pending = self.pending.remove(&event.content_block_index())?;
+                Some(pending.finish())
+            }
+            _ => None,
+        }
+    }
+
+    /// Feeds one stream event, returning the call to tool `T` it completes,
+    /// if any.
+    ///
+    /// A completed tool use for another tool is returned as
+    /// [`ToolUseError::NameMismatch`]; use [`push`](Self::push) to handle
+    /// several tools.
+    pub fn push_tool<T: AgentTool + DeserializeOwned>(
+        &mut self,
+        event: &ConverseStreamOutput,
+    ) -> Option<Result<ToolInvocation<T>, ToolUseError>> {
+        self.push(event)
+            .map(|block| block.and_then(|block| T::from_tool_use(&block)))
+    }
+
+    /// Returns whether a tool use has started but not yet stopped.
+    pub fn is_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+}
+
+#[cfg(feature = "serde-json")]
+impl PendingToolUse {
+    fn finish(self) -> Result<ToolUseBlock, ToolUseError> {
+        let invalid =
+            |source: Box<dyn std::error::Error + Send + Sync>| ToolUseError::InvalidInput {
+                tool_use_id: self.tool_use_id.clone(),
+                source,
+            };
+        let input = if self.input.trim().is_empty() {
+            Value::Object(Default::default())
+        } else {
+            serde_json::from_str(&self.input).map_err(|err| invalid(Box::new(err)))?
+        };
+        let input = to_document(&input).map_err(|err| invalid(Box::new(err)))?;
+        Ok(ToolUseBlock::builder()
+            .tool_use_id(self.tool_use_id)
+            .name(self.name)
+            .input(input)
+            .build()
+            .expect("valid ToolUseBlock"))
+    }
+}
+
+#[cfg(feature = "serde-json")]
+/// Builds the `ToolResultBlock` answering tool use `tool_use_id`.
+///
+/// - An output serializing to a JSON object is sent as a
+///   `ToolResultContentBlock::Json` document.
+/// - A string output is sent as `ToolResultContentBlock::Text`, and any other
+///   output as its JSON text, since Bedrock models expect `json` content to
+///   be an object.
+/// - An error, or an output that fails to serialize, is sent as text with
+///   `ToolResultStatus::Error`.
+///
+/// ```
+/// use derive_agent_tools::bedrock::tool_result;
+///
+/// let ok = tool_result("tooluse_1", Ok::<_, String>(serde_json::json!({ "temp": 21 })));
+/// assert!(ok.status().is_none());
+///
+/// let failed = tool_result("tooluse_2", Err::<(), _>("city not found"));
+/// assert_eq!(failed.content()[0].as_text().unwrap(), "city not found");
+/// ```
+pub fn tool_result<O: Serialize, E: Display>(
+    tool_use_id: impl Into<String>,
+    result: Result<O, E>,
+) -> ToolResultBlock {
+    let (content, status) = match result.map(|output| output_content(&output)) {
+        Ok(Ok(content)) => (content, None),
+        Ok(Err(err)) => (
+            ToolResultContentBlock::Text(format!("failed to serialize tool output: {err}")),
+            Some(ToolResultStatus::Error),
+        ),
+        Err(err) => (
+            ToolResultContentBlock::Text(err.to_string()),
+            Some(ToolResultStatus::Error),
+        ),
+    };
+    ToolResultBlock::builder()
+        .tool_use_id(tool_use_id)
+        .content(content)
+        .set_status(status)
+        .build()
+        .expect("valid ToolResultBlock")
+}
+
+#[cfg(feature = "serde-json")]
+fn output_content<O: Serialize>(
+    output: &O,
+) -> Result<ToolResultContentBlock, Box<dyn std::error::Error>> {
+    Ok(match to_document(output)? {
+        document @ Document::Object(_) => ToolResultContentBlock::Json(document),
+        Document::String(text) => ToolResultContentBlock::Text(text),
+        _ => ToolResultContentBlock::Text(serde_json::to_string(output)?),
+    })
+}
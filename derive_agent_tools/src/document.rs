@@ -0,0 +1,918 @@
+//! serde support for `aws_smithy_types::Document`, the value type Bedrock uses
+//! for tool input.
+//!
+//! [`from_document`] deserializes a tool straight from the `Document`, without
+//! an intermediate `serde_json::Value`, and [`to_document`] serializes any
+//! value into one, e.g. for tool results or replaying a conversation.
+
+use std::collections::{HashMap, hash_map};
+use std::fmt;
+
+use aws_smithy_types::{Document, Number};
+use serde::de::{
+    self, Deserialize, DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess, SeqAccess,
+    VariantAccess, Visitor,
+};
+use serde::forward_to_deserialize_any;
+use serde::ser::{self, Serialize};
+
+/// Deserializes a `T` from a borrowed `Document`.
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use aws_smithy_types::{Document, Number};
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// let doc = Document::Object(HashMap::from([
+///     ("x".to_string(), Document::Number(Number::PosInt(1))),
+///     ("y".to_string(), Document::Number(Number::NegInt(-2))),
+/// ]));
+/// let point: Point = derive_agent_tools::document::from_document(&doc).unwrap();
+/// assert_eq!((point.x, point.y), (1, -2));
+/// ```
+pub fn from_document<'de, T: Deserialize<'de>>(document: &'de Document) -> Result<T, Error> {
+    T::deserialize(DocumentDeserializer::new(document))
+}
+
+/// Serializes `value` into a `Document`.
+///
+/// Unsigned and non-negative integers become `Number::PosInt`, negative ones
+/// `Number::NegInt` and floats `Number::Float`. Enums use serde's externally
+/// tagged representation, as `serde_json` does. Map keys must serialize as
+/// strings, chars, integers or unit variants.
+///
+/// ```
+/// use aws_smithy_types::{Document, Number};
+/// use derive_agent_tools::document::to_document;
+///
+/// assert_eq!(to_document(&-3).unwrap(), Document::Number(Number::NegInt(-3)));
+/// assert_eq!(to_document(&3).unwrap(), Document::Number(Number::PosInt(3)));
+/// assert_eq!(
+///     to_document(&vec![Some(0.5), None]).unwrap(),
+///     Document::Array(vec![Document::Number(Number::Float(0.5)), Document::Null]),
+/// );
+/// ```
+pub fn to_document<T: Serialize + ?Sized>(value: &T) -> Result<Document, Error> {
+    value.serialize(DocumentSerializer)
+}
+
+/// Error returned when a value cannot be converted to or from a `Document`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error(String);
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl de::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self(msg.to_string())
+    }
+}
+
+impl ser::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self(msg.to_string())
+    }
+}
+
+/// A `serde::Deserializer` reading from a borrowed `Document`.
+///
+/// Strings and object keys are borrowed from the document, so types holding
+/// `&str` can be deserialized too.
+#[derive(Debug, Clone, Copy)]
+pub struct DocumentDeserializer<'de> {
+    document: &'de Document,
+}
+
+impl<'de> DocumentDeserializer<'de> {
+    /// Creates a deserializer over `document`.
+    pub fn new(document: &'de Document) -> Self {
+        Self { document }
+    }
+}
+
+impl<'de> de::Deserializer<'de> for DocumentDeserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.document {
+            Document::Null => visitor.visit_unit(),
+            Document::Bool(b) => visitor.visit_bool(*b),
+            Document::Number(Number::PosInt(n)) => visitor.visit_u64(*n),
+            Document::Number(Number::NegInt(n)) => visitor.visit_i64(*n),
+            Document::Number(Number::Float(n)) => visitor.visit_f64(*n),
+            Document::String(s) => visitor.visit_borrowed_str(s),
+            Document::Array(items) => {
+                let mut seq = SeqDeserializer {
+                    items: items.iter(),
+                };
+                let value = visitor.visit_seq(&mut seq)?;
+                match seq.items.len() {
+                    0 => Ok(value),
+                    _ => Err(de::Error::invalid_length(
+                        items.len(),
+                        &"fewer elements in array",
+                    )),
+                }
+            }
+            Document::Object(map) => {
+                let mut entries = MapDeserializer {
+                    entries: map.iter(),
+                    value: None,
+                };
+                let value = visitor.visit_map(&mut entries)?;
+                match entries.entries.len() {
+                    0 => Ok(value),
+                    _ => Err(de::Error::invalid_length(
+                        map.len(),
+                        &"fewer elements in map",
+                    )),
+                }
+            }
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.document {
+            Document::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self.document {
+            Document::String(variant) => {
+                visitor.visit_enum(de::value::BorrowedStrDeserializer::new(variant))
+            }
+            Document::Object(map) if map.len() == 1 => {
+                let (variant, value) = map.iter().next().expect("one entry");
+                visitor.visit_enum(EnumDeserializer { variant, value })
+            }
+            _ => Err(de::Error::invalid_type(
+                unexpected(self.document),
+                &"a string or an object with a single key",
+            )),
+        }
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for DocumentDeserializer<'de> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+fn unexpected(document: &Document) -> de::Unexpected<'_> {
+    match document {
+        Document::Null => de::Unexpected::Unit,
+        Document::Bool(b) => de::Unexpected::Bool(*b),
+        Document::Number(Number::PosInt(n)) => de::Unexpected::Unsigned(*n),
+        Document::Number(Number::NegInt(n)) => de::Unexpected::Signed(*n),
+        Document::Number(Number::Float(n)) => de::Unexpected::Float(*n),
+        Document::String(s) => de::Unexpected::Str(s),
+        Document::Array(_) => de::Unexpected::Seq,
+        Document::Object(_) => de::Unexpected::Map,
+    }
+}
+
+struct SeqDeserializer<'de> {
+    items: std::slice::Iter<'de, Document>,
+}
+
+impl<'de> SeqAccess<'de> for SeqDeserializer<'de> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        self.items
+            .next()
+            .map(|item| seed.deserialize(DocumentDeserializer::new(item)))
+            .transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.items.len())
+    }
+}
+
+struct MapDeserializer<'de> {
+    entries: hash_map::Iter<'de, String, Document>,
+    value: Option<&'de Document>,
+}
+
+impl<'de> MapAccess<'de> for MapDeserializer<'de> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        let Some((key, value)) = self.entries.next() else {
+            return Ok(None);
+        };
+        self.value = Some(value);
+        seed.deserialize(MapKeyDeserializer { key }).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        let value = self
+            .value
+            .take()
+            .ok_or_else(|| de::Error::custom("value requested before key"))?;
+        seed.deserialize(DocumentDeserializer::new(value))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entries.len())
+    }
+}
+
+/// Deserializes an object key. Keys are always strings, but integer keys
+/// (as written for e.g. a `HashMap<u32, _>`) are parsed back from them.
+struct MapKeyDeserializer<'de> {
+    key: &'de str,
+}
+
+macro_rules! deserialize_integer_key {
+    ($($method:ident => $visit:ident),* $(,)?) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+                match self.key.parse() {
+                    Ok(n) => visitor.$visit(n),
+                    Err(_) => Err(de::Error::invalid_type(
+                        de::Unexpected::Str(self.key),
+                        &visitor,
+                    )),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for MapKeyDeserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_borrowed_str(self.key)
+    }
+
+    deserialize_integer_key! {
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_enum(de::value::BorrowedStrDeserializer::new(self.key))
+    }
+
+    forward_to_deserialize_any! {
+        bool f32 f64 char str string bytes byte_buf unit unit_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+struct EnumDeserializer<'de> {
+    variant: &'de str,
+    value: &'de Document,
+}
+
+impl<'de> EnumAccess<'de> for EnumDeserializer<'de> {
+    type Error = Error;
+    type Variant = DocumentDeserializer<'de>;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self::Variant), Error> {
+        let variant = seed.deserialize(de::value::BorrowedStrDeserializer::new(self.variant))?;
+        Ok((variant, DocumentDeserializer::new(self.value)))
+    }
+}
+
+impl<'de> VariantAccess<'de> for DocumentDeserializer<'de> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        match self.document {
+            Document::Null => Ok(()),
+            other => Err(de::Error::invalid_type(unexpected(other), &"unit variant")),
+        }
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Error> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_seq(self, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_map(self, visitor)
+    }
+}
+
+/// A `serde::Serializer` producing a `Document`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DocumentSerializer;
+
+fn signed(n: i64) -> Document {
+    Document::Number(if n < 0 {
+        Number::NegInt(n)
+    } else {
+        Number::PosInt(n as u64)
+    })
+}
+
+fn unsigned(n: u64) -> Document {
+    Document::Number(Number::PosInt(n))
+}
+
+fn tagged(variant: &str, value: Document) -> Document {
+    Document::Object(HashMap::from([(variant.to_string(), value)]))
+}
+
+impl ser::Serializer for DocumentSerializer {
+    type Ok = Document;
+    type Error = Error;
+    type SerializeSeq = SerializeArray;
+    type SerializeTuple = SerializeArray;
+    type SerializeTupleStruct = SerializeArray;
+    type SerializeTupleVariant = SerializeArray;
+    type SerializeMap = SerializeObject;
+    type SerializeStruct = SerializeObject;
+    type SerializeStructVariant = SerializeObject;
+
+    fn serialize_bool(self, v: bool) -> Result<Document, Error> {
+        Ok(Document::Bool(v))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Document, Error> {
+        Ok(signed(v.into()))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Document, Error> {
+        Ok(signed(v.into()))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Document, Error> {
+        Ok(signed(v.into()))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Document, Error> {
+        Ok(signed(v))
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<Document, Error> {
+        if let Ok(v) = u64::try_from(v) {
+            Ok(unsigned(v))
+        } else if let Ok(v) = i64::try_from(v) {
+            Ok(signed(v))
+        } else {
+            Err(ser::Error::custom(format!(
+                "{v} is out of range for a Document number"
+            )))
+        }
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Document, Error> {
+        Ok(unsigned(v.into()))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Document, Error> {
+        Ok(unsigned(v.into()))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Document, Error> {
+        Ok(unsigned(v.into()))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Document, Error> {
+        Ok(unsigned(v))
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<Document, Error> {
+        u64::try_from(v)
+            .map(unsigned)
+            .map_err(|_| ser::Error::custom(format!("{v} is out of range for a Document number")))
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Document, Error> {
+        Ok(Document::Number(Number::Float(v.into())))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Document, Error> {
+        Ok(Document::Number(Number::Float(v)))
+    }
+
+    fn serialize_char(self, v: char) -> Result<Document, Error> {
+        Ok(Document::String(v.to_string()))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Document, Error> {
+        Ok(Document::String(v.to_string()))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Document, Error> {
+        Ok(Document::Array(
+            v.iter().map(|b| unsigned((*b).into())).collect(),
+        ))
+    }
+
+    fn serialize_none(self) -> Result<Document, Error> {
+        Ok(Document::Null)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Document, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Document, Error> {
+        Ok(Document::Null)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Document, Error> {
+        Ok(Document::Null)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<Document, Error> {
+        Ok(Document::String(variant.to_string()))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Document, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Document, Error> {
+        Ok(tagged(variant, value.serialize(self)?))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SerializeArray, Error> {
+        Ok(SerializeArray {
+            variant: None,
+            items: Vec::with_capacity(len.unwrap_or(0)),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SerializeArray, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SerializeArray, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeArray, Error> {
+        Ok(SerializeArray {
+            variant: Some(variant),
+            items: Vec::with_capacity(len),
+        })
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<SerializeObject, Error> {
+        Ok(SerializeObject {
+            variant: None,
+            entries: HashMap::with_capacity(len.unwrap_or(0)),
+            key: None,
+        })
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<SerializeObject, Error> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeObject, Error> {
+        Ok(SerializeObject {
+            variant: Some(variant),
+            entries: HashMap::with_capacity(len),
+            key: None,
+        })
+    }
+}
+
+/// Builds a `Document::Array`, wrapped as `{variant: [...]}` for tuple
+/// variants.
+pub struct SerializeArray {
+    variant: Option<&'static str>,
+    items: Vec<Document>,
+}
+
+impl SerializeArray {
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.items.push(value.serialize(DocumentSerializer)?);
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Document, Error> {
+        let array = Document::Array(self.items);
+        Ok(match self.variant {
+            Some(variant) => tagged(variant, array),
+            None => array,
+        })
+    }
+}
+
+impl ser::SerializeSeq for SerializeArray {
+    type Ok = Document;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Document, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTuple for SerializeArray {
+    type Ok = Document;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Document, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeArray {
+    type Ok = Document;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Document, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleVariant for SerializeArray {
+    type Ok = Document;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Document, Error> {
+        self.finish()
+    }
+}
+
+/// Builds a `Document::Object`, wrapped as `{variant: {...}}` for struct
+/// variants.
+pub struct SerializeObject {
+    variant: Option<&'static str>,
+    entries: HashMap<String, Document>,
+    key: Option<String>,
+}
+
+impl SerializeObject {
+    fn insert<T: Serialize + ?Sized>(&mut self, key: String, value: &T) -> Result<(), Error> {
+        self.entries
+            .insert(key, value.serialize(DocumentSerializer)?);
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Document, Error> {
+        let object = Document::Object(self.entries);
+        Ok(match self.variant {
+            Some(variant) => tagged(variant, object),
+            None => object,
+        })
+    }
+}
+
+impl ser::SerializeMap for SerializeObject {
+    type Ok = Document;
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Error> {
+        self.key = Some(key.serialize(MapKeySerializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| ser::Error::custom("value serialized before key"))?;
+        self.insert(key, value)
+    }
+
+    fn end(self) -> Result<Document, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStruct for SerializeObject {
+    type Ok = Document;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.insert(key.to_string(), value)
+    }
+
+    fn end(self) -> Result<Document, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStructVariant for SerializeObject {
+    type Ok = Document;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.insert(key.to_string(), value)
+    }
+
+    fn end(self) -> Result<Document, Error> {
+        self.finish()
+    }
+}
+
+/// Serializes map keys, which a `Document` object stores as strings.
+struct MapKeySerializer;
+
+fn key_must_be_a_string() -> Error {
+    ser::Error::custom("map key must be a string")
+}
+
+impl ser::Serializer for MapKeySerializer {
+    type Ok = String;
+    type Error = Error;
+    type SerializeSeq = ser::Impossible<String, Error>;
+    type SerializeTuple = ser::Impossible<String, Error>;
+    type SerializeTupleStruct = ser::Impossible<String, Error>;
+    type SerializeTupleVariant = ser::Impossible<String, Error>;
+    type SerializeMap = ser::Impossible<String, Error>;
+    type SerializeStruct = ser::Impossible<String, Error>;
+    type SerializeStructVariant = ser::Impossible<String, Error>;
+
+    fn serialize_str(self, v: &str) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_char(self, v: char) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<String, Error> {
+        Ok(variant.to_string())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<String, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_bool(self, _v: bool) -> Result<String, Error> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_f32(self, _v: f32) -> Result<String, Error> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_f64(self, _v: f64) -> Result<String, Error> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> Result<String, Error> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_none(self) -> Result<String, Error> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, _value: &T) -> Result<String, Error> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_unit(self) -> Result<String, Error> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<String, Error> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<String, Error> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Error> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Error> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Error> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Error> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Error> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Error> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Error> {
+        Err(key_must_be_a_string())
+    }
+}
@@ -0,0 +1,148 @@
+//! Pluggable tool envelopes.
+//!
+//! A [`ToolFormat`] turns a tool's metadata and [`Schema`] into a provider's
+//! tool definition and parses that provider's tool calls back into the tool.
+//! Any derived tool can be rendered through one with
+//! [`AgentTool::definition`]:
+//!
+//! ```
+//! use derive_agent_tools::AgentTool;
+//! use derive_agent_tools::format::{ToolFormat, ToolInfo};
+//! use serde::de::DeserializeOwned;
+//! use serde::Deserialize;
+//!
+//! /// `name|description` definitions, with calls passed as JSON text.
+//! struct Pipe;
+//!
+//! impl ToolFormat for Pipe {
+//!     type Definition = String;
+//!     type Call = str;
+//!     type Error = String;
+//!
+//!     fn definition(tool: ToolInfo) -> String {
+//!         format!("{}|{}", tool.name, tool.description.unwrap_or_default())
+//!     }
+//!
+//!     # #[cfg(feature = "serde-json")]
+//!     fn parse_call<T: AgentTool + DeserializeOwned>(call: &str) -> Result<T, String> {
+//!         serde_json::from_str(call).map_err(|err| err.to_string())
+//!     }
+//!     # #[cfg(not(feature = "serde-json"))]
+//!     # fn parse_call<T: AgentTool + DeserializeOwned>(_: &str) -> Result<T, String> {
+//!     #     Err(String::new())
+//!     # }
+//! }
+//!
+//! #[derive(AgentTool, Deserialize)]
+//! #[tool(name = "ping", description = "Check the connection")]
+//! struct PingTool {
+//!     count: Option<u32>,
+//! }
+//!
+//! assert_eq!(PingTool::definition::<Pipe>(), "ping|Check the connection");
+//! # #[cfg(feature = "serde-json")]
+//! assert_eq!(PingTool::parse_call::<Pipe>(r#"{"count": 3}"#).unwrap().count, Some(3));
+//! ```
+
+use serde::de::DeserializeOwned;
+
+use crate::{AgentTool, Schema, ToolAnnotations};
+
+/// A provider's tool envelope: how tools are declared to it and how its tool
+/// calls look.
+pub trait ToolFormat {
+    /// A tool declaration as the provider expects it.
+    type Definition;
+    /// The payload of one tool call.
+    type Call: ?Sized;
+    /// Error returned when a call cannot be parsed into a tool.
+    type Error;
+
+    /// Renders the declaration of a tool.
+    fn definition(tool: ToolInfo) -> Self::Definition;
+
+    /// Parses a call to tool `T`.
+    fn parse_call<T: AgentTool + DeserializeOwned>(call: &Self::Call) -> Result<T, Self::Error>;
+}
+
+/// Everything a [`ToolFormat`] needs to declare a tool.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolInfo {
+    pub name: &'static str,
+    pub title: Option<&'static str>,
+    pub description: Option<&'static str>,
+    pub annotations: ToolAnnotations,
+    /// The schema of the tool's input.
+    pub schema: Schema,
+}
+
+impl ToolInfo {
+    /// Collects the metadata of tool `T`.
+    pub fn of<T: AgentTool>() -> Self {
+        Self {
+            name: T::tool_name(),
+            title: T::tool_title(),
+            description: T::tool_description(),
+            annotations: T::tool_annotations(),
+            schema: T::tool_schema(),
+        }
+    }
+}
+
+/// Provider-neutral function declarations,
+/// `{"name", "description", "parameters"}`, with the JSON arguments object
+/// as the call.
+#[cfg(feature = "serde-json")]
+#[derive(Debug, Clone, Copy)]
+pub struct JsonSchema;
+
+#[cfg(feature = "serde-json")]
+impl ToolFormat for JsonSchema {
+    type Definition = serde_json::Value;
+    type Call = serde_json::Value;
+    type Error = serde_json::Error;
+
+    fn definition(tool: ToolInfo) -> serde_json::Value {
+        let mut definition = serde_json::Map::new();
+        definition.insert("name".to_string(), tool.name.into());
+        if let Some(description) = tool.description {
+            definition.insert("description".to_string(), description.into());
+        }
+        definition.insert("parameters".to_string(), tool.schema.to_json());
+        serde_json::Value::Object(definition)
+    }
+
+    fn parse_call<T: AgentTool + DeserializeOwned>(
+        call: &serde_json::Value,
+    ) -> Result<T, serde_json::Error> {
+        T::deserialize(call)
+    }
+}
+
+/// AWS Bedrock Converse: a `ToolSpecification` per tool and a `ToolUseBlock`
+/// per call.
+#[cfg(feature = "bedrock")]
+#[derive(Debug, Clone, Copy)]
+pub struct Bedrock;
+
+#[cfg(feature = "bedrock")]
+impl ToolFormat for Bedrock {
+    type Definition = aws_sdk_bedrockruntime::types::ToolSpecification;
+    type Call = aws_sdk_bedrockruntime::types::ToolUseBlock;
+    type Error = crate::bedrock::ToolUseError;
+
+    fn definition(tool: ToolInfo) -> Self::Definition {
+        aws_sdk_bedrockruntime::types::ToolSpecification::builder()
+            .name(tool.name)
+            .set_description(tool.description.map(|s| s.to_string()))
+            .input_schema(aws_sdk_bedrockruntime::types::ToolInputSchema::Json(
+                tool.schema.to_document(),
+            ))
+            .build()
+            .expect("valid ToolSpecification")
+    }
+
+    fn parse_call<T: AgentTool + DeserializeOwned>(call: &Self::Call) -> Result<T, Self::Error> {
+        T::from_tool_use(call).map(|invocation| invocation.input)
+    }
+}
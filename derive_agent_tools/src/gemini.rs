@@ -0,0 +1,271 @@
+//! Google Gemini / Vertex AI function declarations.
+//!
+//! Gemini accepts an OpenAPI 3.0 subset rather than JSON Schema.
+//! [`gemini_schema`] converts the derived JSON Schema into that dialect and
+//! [`AgentTool::gemini_function_declaration`](crate::AgentTool::gemini_function_declaration)
+//! wraps it in a `functionDeclarations` entry.
+
+use serde_json::{Map, Value, json};
+
+/// Keys Gemini's `Schema` object understands besides `type`, `format`,
+/// `nullable`, `enum`, `properties`, `items` and `anyOf`, which are rewritten.
+const PASSTHROUGH_KEYS: &[&str] = &[
+    "title",
+    "description",
+    "required",
+    "minItems",
+    "maxItems",
+    "minProperties",
+    "maxProperties",
+    "minLength",
+    "maxLength",
+    "pattern",
+    "minimum",
+    "maximum",
+    "default",
+    "example",
+    "propertyOrdering",
+];
+
+/// Converts a JSON Schema into Gemini's OpenAPI subset.
+///
+/// - `type` names are uppercased (`"string"` becomes `"STRING"`).
+/// - `"null"` in a type array, a `{"type": "null"}` branch or a `null` enum
+///   value becomes `nullable: true`.
+/// - `oneOf` becomes `anyOf`; a union left with a single branch is inlined.
+/// - `const` becomes a single-value `enum`.
+/// - `format` is kept only where Gemini supports it (`enum`/`date-time` on
+///   strings, `int32`/`int64` on integers, `float`/`double` on numbers).
+/// - `prefixItems` (tuples) becomes `items`: the shared item schema, or an
+///   `anyOf` of the distinct ones.
+/// - `allOf` entries are merged into the object: their properties are added,
+///   and the properties of union entries are added as optional.
+/// - Free-form maps and untyped values (`{}`), which Gemini cannot describe,
+///   become a `STRING` whose description asks for JSON text.
+/// - Any other keyword outside the subset is dropped.
+pub fn gemini_schema(schema: &Value) -> Value {
+    let (mut out, nullable) = convert(schema);
+    if nullable {
+        out.insert("nullable".to_string(), Value::Bool(true));
+    }
+    Value::Object(out)
+}
+
+/// Converts one schema node, returning whether it allowed `null`.
+fn convert(schema: &Value) -> (Map<String, Value>, bool) {
+    let mut out = Map::new();
+    let Value::Object(map) = schema else {
+        return (out, false);
+    };
+    if let Some(Value::Array(parts)) = map.get("allOf") {
+        return convert(&merge_all_of(map, parts));
+    }
+    let mut nullable = map.get("nullable") == Some(&Value::Bool(true));
+
+    let mut typ = None;
+    match map.get("type") {
+        Some(Value::String(t)) if t == "null" => nullable = true,
+        Some(Value::String(t)) => typ = Some(t.as_str()),
+        Some(Value::Array(types)) => {
+            for t in types.iter().filter_map(Value::as_str) {
+                if t == "null" {
+                    nullable = true;
+                } else if typ.is_none() {
+                    typ = Some(t);
+                }
+            }
+        }
+        _ => {}
+    }
+
+    let values = match (map.get("enum"), map.get("const")) {
+        (Some(Value::Array(values)), _) => Some(values.clone()),
+        (None, Some(value)) => Some(vec![value.clone()]),
+        _ => None,
+    };
+    if let Some(values) = values {
+        nullable |= values.iter().any(Value::is_null);
+        let values: Vec<Value> = values.into_iter().filter(|v| !v.is_null()).collect();
+        out.insert("enum".to_string(), Value::Array(values));
+        typ.get_or_insert("string");
+    }
+
+    let free_form = match typ {
+        Some("object") => {
+            !matches!(map.get("properties"), Some(Value::Object(props)) if !props.is_empty())
+                && matches!(
+                    map.get("additionalProperties"),
+                    Some(Value::Object(_) | Value::Bool(true))
+                )
+        }
+        Some(_) => false,
+        None => ["type", "enum", "const", "anyOf", "oneOf"]
+            .iter()
+            .all(|key| !map.contains_key(*key)),
+    };
+    if !free_form && let Some(t) = typ {
+        out.insert("type".to_string(), Value::String(t.to_ascii_uppercase()));
+        if let Some(Value::String(format)) = map.get("format") {
+            let supported = match t {
+                "string" => matches!(format.as_str(), "enum" | "date-time"),
+                "integer" => matches!(format.as_str(), "int32" | "int64"),
+                "number" => matches!(format.as_str(), "float" | "double"),
+                _ => false,
+            };
+            if supported {
+                out.insert("format".to_string(), Value::String(format.clone()));
+            }
+        }
+    }
+
+    if let Some(Value::Object(props)) = map.get("properties") {
+        let props = props
+            .iter()
+            .map(|(name, prop)| (name.clone(), gemini_schema(prop)))
+            .collect();
+        out.insert("properties".to_string(), Value::Object(props));
+    }
+    if let Some(items) = map.get("items") {
+        out.insert("items".to_string(), gemini_schema(items));
+    } else if let Some(Value::Array(positions)) = map.get("prefixItems") {
+        let mut distinct: Vec<Value> = Vec::new();
+        for position in positions.iter().map(gemini_schema) {
+            if !distinct.contains(&position) {
+                distinct.push(position);
+            }
+        }
+        let items = match distinct.len() {
+            1 => distinct.remove(0),
+            _ => json!({ "anyOf": distinct }),
+        };
+        out.insert("items".to_string(), items);
+    }
+
+    if let Some(Value::Array(branches)) = map.get("anyOf").or_else(|| map.get("oneOf")) {
+        let mut converted = Vec::with_capacity(branches.len());
+        for branch in branches {
+            let (branch, branch_nullable) = convert(branch);
+            nullable |= branch_nullable;
+            // A bare `{"type": "null"}` branch only contributes nullability.
+            if !branch.is_empty() {
+                converted.push(Value::Object(branch));
+            }
+        }
+        if converted.len() == 1 && !out.contains_key("type") {
+            if let Some(Value::Object(branch)) = converted.pop() {
+                for (key, value) in branch {
+                    out.entry(key).or_insert(value);
+                }
+            }
+        } else if !converted.is_empty() {
+            out.insert("anyOf".to_string(), Value::Array(converted));
+        }
+    }
+
+    for key in PASSTHROUGH_KEYS {
+        if let Some(value) = map.get(*key) {
+            out.insert(key.to_string(), value.clone());
+        }
+    }
+
+    if free_form {
+        let what = match map.get("type") {
+            Some(_) => "A JSON object",
+            None => "Any JSON value",
+        };
+        let note = match map.get("description").and_then(Value::as_str) {
+            Some(description) => format!("{description}\n\n{what}, encoded as a string."),
+            None => format!("{what}, encoded as a string."),
+        };
+        out.insert("type".to_string(), Value::String("STRING".to_string()));
+        out.insert("description".to_string(), Value::String(note));
+    }
+
+    (out, nullable)
+}
+
+/// Folds the `allOf` entries of `map` into a single object schema. Plain
+/// object entries add their properties and `required` list; union entries
+/// add the properties of every branch, none of them required.
+fn merge_all_of(map: &Map<String, Value>, parts: &[Value]) -> Value {
+    let mut merged = map.clone();
+    merged.remove("allOf");
+    let mut properties = match merged.remove("properties") {
+        Some(Value::Object(props)) => props,
+        _ => Map::new(),
+    };
+    let mut required = match merged.remove("required") {
+        Some(Value::Array(names)) => names,
+        _ => Vec::new(),
+    };
+
+    let mut add = |part: &Value, keep_required: bool| {
+        if let Some(Value::Object(props)) = part.get("properties") {
+            for (name, prop) in props {
+                match properties.get_mut(name) {
+                    // A tag shared by several branches accepts every branch's value.
+                    Some(Value::Object(existing)) => {
+                        if let (Some(Value::Array(values)), Some(Value::Array(more))) =
+                            (existing.get_mut("enum"), prop.get("enum"))
+                        {
+                            for value in more {
+                                if !values.contains(value) {
+                                    values.push(value.clone());
+                                }
+                            }
+                        }
+                    }
+                    _ => {
+                        properties.insert(name.clone(), prop.clone());
+                    }
+                }
+            }
+        }
+        if keep_required && let Some(Value::Array(names)) = part.get("required") {
+            for name in names {
+                if !required.contains(name) {
+                    required.push(name.clone());
+                }
+            }
+        }
+    };
+    for part in parts {
+        add(part, true);
+        for branches in ["anyOf", "oneOf"].iter().filter_map(|key| part.get(*key)) {
+            for branch in branches.as_array().into_iter().flatten() {
+                add(branch, false);
+                // An optional flattened union nests its union one level down.
+                for nested in ["anyOf", "oneOf"].iter().filter_map(|key| branch.get(*key)) {
+                    for branch in nested.as_array().into_iter().flatten() {
+                        add(branch, false);
+                    }
+                }
+            }
+        }
+    }
+
+    merged.insert("type".to_string(), Value::String("object".to_string()));
+    merged.insert("properties".to_string(), Value::Object(properties));
+    if !required.is_empty() {
+        merged.insert("required".to_string(), Value::Array(required));
+    }
+    Value::Object(merged)
+}
+
+/// `{"name": ..., "description": ..., "parameters": ...}`
+pub(crate) fn function_declaration(
+    name: &str,
+    description: Option<&str>,
+    parameters: &Value,
+) -> Value {
+    let mut map = Map::new();
+    map.insert("name".to_string(), Value::String(name.to_string()));
+    if let Some(description) = description {
+        map.insert(
+            "description".to_string(),
+            Value::String(description.to_string()),
+        );
+    }
+    map.insert("parameters".to_string(), gemini_schema(parameters));
+    Value::Object(map)
+}
@@ -0,0 +1,85 @@
+//! Handler traits that run a tool once its input has been parsed.
+
+use std::fmt::Display;
+use std::future::Future;
+
+use serde::Serialize;
+
+use crate::AgentTool;
+
+/// Runs a tool, producing a serializable output for the model.
+///
+/// `Ctx` is whatever shared state the tool needs (clients, credentials, ...)
+/// and defaults to `()`. Implement this trait directly for async tools,
+/// implement [`SyncAgentToolHandler`] for blocking ones, or let the derive do
+/// it with `#[tool(handler = path)]` / `#[tool(async_handler = path)]`.
+///
+/// ```
+/// use derive_agent_tools::{AgentTool, AgentToolHandler};
+/// use serde::Deserialize;
+///
+/// #[derive(AgentTool, Deserialize)]
+/// struct Add {
+///     #[tool(required)]
+///     a: i64,
+///     #[tool(required)]
+///     b: i64,
+/// }
+///
+/// impl AgentToolHandler for Add {
+///     type Output = i64;
+///     type Error = String;
+///
+///     async fn call(self, _ctx: &()) -> Result<i64, String> {
+///         self.a.checked_add(self.b).ok_or_else(|| "overflow".to_string())
+///     }
+/// }
+/// ```
+pub trait AgentToolHandler<Ctx = ()>: AgentTool {
+    /// Result returned to the model on success.
+    type Output: Serialize;
+    /// Error reported to the model on failure.
+    type Error: Display;
+
+    /// Runs the tool with its parsed input.
+    fn call(self, ctx: &Ctx) -> impl Future<Output = Result<Self::Output, Self::Error>> + Send;
+}
+
+/// Blocking variant of [`AgentToolHandler`].
+///
+/// Every `SyncAgentToolHandler` is also an `AgentToolHandler` whose future
+/// completes on first poll.
+pub trait SyncAgentToolHandler<Ctx = ()>: AgentTool {
+    /// Result returned to the model on success.
+    type Output: Serialize;
+    /// Error reported to the model on failure.
+    type Error: Display;
+
+    /// Runs the tool with its parsed input.
+    fn call_sync(self, ctx: &Ctx) -> Result<Self::Output, Self::Error>;
+}
+
+impl<T, Ctx> AgentToolHandler<Ctx> for T
+where
+    T: SyncAgentToolHandler<Ctx> + Send,
+    Ctx: Sync,
+{
+    type Output = T::Output;
+    type Error = T::Error;
+
+    async fn call(self, ctx: &Ctx) -> Result<Self::Output, Self::Error> {
+        self.call_sync(ctx)
+    }
+}
+
+/// Converts the result of a `#[tool(handler = ...)]` function into the
+/// handler traits' erased output.
+pub fn erase_output<O: Serialize, E: Display>(
+    result: Result<O, E>,
+) -> Result<serde_json::Value, String> {
+    match result {
+        Ok(output) => serde_json::to_value(output)
+            .map_err(|err| format!("failed to serialize tool output: {err}")),
+        Err(err) => Err(err.to_string()),
+    }
+}
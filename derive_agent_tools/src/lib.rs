@@ -3,9 +3,16 @@
 //! Single-install facade crate that re-exports the derive macros and provides
 //! hidden re-exports used by macro expansions. Users only add this crate.
 //!
+//! `#[derive(AgentTool)]` implements the [`AgentTool`] trait, so code can be
+//! generic over tools (`fn register<T: AgentTool>()`).
+//!
 //! Features
 //! - `serde-json` (default): enables JSON schema helpers
 //! - `bedrock` (default): enables AWS Bedrock ToolSpecification helpers
+//! - `openai`: enables OpenAI Chat Completions / Responses function tools
+//! - `anthropic`: enables Anthropic Messages API tool definitions
+//! - `gemini`: enables Google Gemini / Vertex AI function declarations
+//! - `mcp`: enables Model Context Protocol tool descriptors and a stdio server
 //!
 //! Example
 //! ```
@@ -15,9 +22,9 @@
 //! #[derive(AgentTool, Deserialize)]
 //! #[tool(description = "A tool to get the weather")]
 //! struct WeatherTool {
-//!     #[tool(required, description = "The latitude of the location")]
+//!     #[tool(description = "The latitude of the location")]
 //!     latitude: f64,
-//!     #[tool(required, description = "The longitude of the location")]
+//!     #[tool(description = "The longitude of the location")]
 //!     longitude: f64,
 //! }
 //!
@@ -27,16 +34,106 @@
 //! let _schema = WeatherTool::tool_schema_json();
 //! ```
 
+#[cfg(feature = "anthropic")]
+pub mod anthropic;
+#[cfg(feature = "bedrock")]
+pub mod bedrock;
+#[cfg(feature = "bedrock")]
+pub mod document;
+#[cfg(any(feature = "serde-json", feature = "bedrock"))]
+pub mod format;
+#[cfg(feature = "gemini")]
+pub mod gemini;
+#[cfg(feature = "serde-json")]
+mod handler;
+#[cfg(feature = "mcp")]
+pub mod mcp;
+#[cfg(feature = "openai")]
+pub mod openai;
+mod parameter;
+#[cfg(feature = "serde-json")]
+pub mod partial;
+pub mod schema;
+mod tool;
+#[cfg(feature = "serde-json")]
+mod toolbox;
+
 pub use derive_agent_tools_internals::{AgentTool, AgentToolParameter};
+#[cfg(feature = "serde-json")]
+pub use handler::{AgentToolHandler, SyncAgentToolHandler};
+pub use parameter::AgentToolParameter;
+#[cfg(feature = "serde-json")]
+pub use partial::PartialAgentTool;
+pub use schema::Schema;
+pub use tool::{AgentTool, DynAgentTool, ToolAnnotations, ToolParseError, ToolType};
+#[cfg(feature = "serde-json")]
+pub use toolbox::{ToolBox, ToolBoxError};
+
+// Expand their input only when the facade's feature is enabled. Derived code
+// uses these instead of `#[cfg(feature = ...)]`, which would be evaluated
+// against the features of the crate invoking the derive.
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "serde-json")]
+macro_rules! __if_serde_json {
+    ($($item:tt)*) => { $($item)* };
+}
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(feature = "serde-json"))]
+macro_rules! __if_serde_json {
+    ($($item:tt)*) => {};
+}
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "bedrock")]
+macro_rules! __if_bedrock {
+    ($($item:tt)*) => { $($item)* };
+}
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(feature = "bedrock"))]
+macro_rules! __if_bedrock {
+    ($($item:tt)*) => {};
+}
+
+// Handler impls erase their output to `serde_json::Value`. Without
+// `serde-json` the `#[tool(handler)]` attribute is an error rather than
+// silently ignored.
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "serde-json")]
+macro_rules! __handler_impl {
+    ($($item:tt)*) => { $($item)* };
+}
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(feature = "serde-json"))]
+macro_rules! __handler_impl {
+    ($($item:tt)*) => {
+        ::core::compile_error!(
+            "`#[tool(handler = ...)]` and `#[tool(async_handler = ...)]` require the `serde-json` feature of `derive_agent_tools`"
+        );
+    };
+}
 
 #[doc(hidden)]
 pub mod __macro_support {
+    pub use crate::{
+        __handler_impl as handler_impl, __if_bedrock as if_bedrock,
+        __if_serde_json as if_serde_json,
+    };
+
     #[cfg(feature = "bedrock")]
     pub use aws_sdk_bedrockruntime;
     #[cfg(feature = "bedrock")]
     pub use aws_smithy_types;
-    #[cfg(feature = "serde-json")]
+    #[cfg(any(feature = "serde-json", feature = "bedrock"))]
     pub use serde;
     #[cfg(feature = "serde-json")]
     pub use serde_json;
+
+    #[cfg(feature = "serde-json")]
+    pub use crate::handler::erase_output as erase_handler_output;
+    pub use crate::schema::{BranchMeta, PropertyMeta, SchemaMeta, SchemaType};
 }
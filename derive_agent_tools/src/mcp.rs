@@ -0,0 +1,254 @@
+//! Model Context Protocol (MCP) tool descriptors and a minimal stdio server.
+//!
+//! [`AgentTool::mcp_tool`](crate::AgentTool::mcp_tool) renders a tool as an
+//! MCP `Tool` object. [`McpServer`] answers `initialize`, `tools/list` and
+//! `tools/call` over newline-delimited JSON-RPC 2.0, dispatching calls to
+//! handlers registered per tool.
+
+use std::fmt::Display;
+use std::io::{self, BufRead, Write};
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value, json};
+
+use crate::{AgentTool, ToolAnnotations};
+
+/// The newest protocol revision this server speaks.
+pub const LATEST_PROTOCOL_VERSION: &str = "2025-06-18";
+
+/// Protocol revisions accepted from clients during `initialize`.
+const SUPPORTED_PROTOCOL_VERSIONS: &[&str] = &[LATEST_PROTOCOL_VERSION, "2025-03-26", "2024-11-05"];
+
+// JSON-RPC error codes
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// `{"name", "title", "description", "inputSchema", "annotations"}`
+pub(crate) fn tool_descriptor(
+    name: &str,
+    title: Option<&str>,
+    description: Option<&str>,
+    input_schema: Value,
+    annotations: ToolAnnotations,
+) -> Value {
+    let mut map = Map::new();
+    map.insert("name".to_string(), Value::String(name.to_string()));
+    if let Some(title) = title {
+        map.insert("title".to_string(), Value::String(title.to_string()));
+    }
+    if let Some(description) = description {
+        map.insert(
+            "description".to_string(),
+            Value::String(description.to_string()),
+        );
+    }
+    map.insert("inputSchema".to_string(), input_schema);
+
+    let mut hints = Map::new();
+    if let Some(title) = title {
+        hints.insert("title".to_string(), Value::String(title.to_string()));
+    }
+    for (key, hint) in [
+        ("readOnlyHint", annotations.read_only),
+        ("destructiveHint", annotations.destructive),
+        ("idempotentHint", annotations.idempotent),
+        ("openWorldHint", annotations.open_world),
+    ] {
+        if let Some(hint) = hint {
+            hints.insert(key.to_string(), Value::Bool(hint));
+        }
+    }
+    if !hints.is_empty() {
+        map.insert("annotations".to_string(), Value::Object(hints));
+    }
+    Value::Object(map)
+}
+
+type Handler = Box<dyn Fn(&Value) -> Value + Send + Sync>;
+
+struct RegisteredTool {
+    name: &'static str,
+    descriptor: Value,
+    handler: Handler,
+}
+
+/// A minimal MCP server exposing registered tools.
+///
+/// ```no_run
+/// use derive_agent_tools::AgentTool;
+/// use derive_agent_tools::mcp::McpServer;
+/// use serde::Deserialize;
+///
+/// #[derive(AgentTool, Deserialize)]
+/// #[tool(description = "Add two numbers", read_only)]
+/// struct Add {
+///     #[tool(required)]
+///     a: i64,
+///     #[tool(required)]
+///     b: i64,
+/// }
+///
+/// let mut server = McpServer::new("calculator", "0.1.0");
+/// server.register(|args: Add| Ok::<_, String>(args.a + args.b));
+/// server.serve_stdio()?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub struct McpServer {
+    name: String,
+    version: String,
+    tools: Vec<RegisteredTool>,
+}
+
+impl McpServer {
+    /// Creates a server reporting `name` and `version` as its `serverInfo`.
+    pub fn new(name: impl Into<String>, version: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            version: version.into(),
+            tools: Vec::new(),
+        }
+    }
+
+    /// Registers tool `T`, answering its `tools/call` requests with `handler`.
+    ///
+    /// A successful result is serialized as JSON into a text content block,
+    /// and also returned as `structuredContent` when it is a JSON object. An
+    /// error, or arguments that do not deserialize into `T`, produce a result
+    /// with `isError: true`. Registering a tool name twice replaces the
+    /// earlier handler.
+    pub fn register<T, F, O, E>(&mut self, handler: F) -> &mut Self
+    where
+        T: AgentTool + DeserializeOwned,
+        F: Fn(T) -> Result<O, E> + Send + Sync + 'static,
+        O: Serialize,
+        E: Display,
+    {
+        let handler: Handler = Box::new(move |arguments| {
+            let args = match T::deserialize(arguments) {
+                Ok(args) => args,
+                Err(err) => return error_result(format!("invalid arguments: {err}")),
+            };
+            match handler(args).map(|output| serde_json::to_value(output)) {
+                Ok(Ok(output)) => success_result(output),
+                Ok(Err(err)) => error_result(format!("failed to serialize result: {err}")),
+                Err(err) => error_result(err.to_string()),
+            }
+        });
+        self.tools.retain(|tool| tool.name != T::tool_name());
+        self.tools.push(RegisteredTool {
+            name: T::tool_name(),
+            descriptor: T::mcp_tool(),
+            handler,
+        });
+        self
+    }
+
+    /// Returns the `Tool` descriptors answered by `tools/list`.
+    pub fn tools(&self) -> Vec<Value> {
+        self.tools.iter().map(|t| t.descriptor.clone()).collect()
+    }
+
+    /// Handles one JSON-RPC message, returning the response to send, if any.
+    ///
+    /// Notifications (messages without an `id`) never produce a response.
+    pub fn handle_message(&self, message: &Value) -> Option<Value> {
+        let id = message.get("id").cloned();
+        let Some(method) = message.get("method").and_then(Value::as_str) else {
+            return id.map(|id| error_response(id, INVALID_REQUEST, "missing method"));
+        };
+        let id = id?;
+        let params = message.get("params").unwrap_or(&Value::Null);
+
+        let result = match method {
+            "initialize" => Ok(self.initialize(params)),
+            "ping" => Ok(json!({})),
+            "tools/list" => Ok(json!({ "tools": self.tools() })),
+            "tools/call" => self.call(params),
+            _ => Err((METHOD_NOT_FOUND, format!("method not found: {method}"))),
+        };
+        Some(match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err((code, message)) => error_response(id, code, &message),
+        })
+    }
+
+    /// Serves newline-delimited JSON-RPC messages from `reader` until it is
+    /// exhausted, writing one response line per request to `writer`.
+    pub fn serve<R: BufRead, W: Write>(&self, reader: R, mut writer: W) -> io::Result<()> {
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let response = match serde_json::from_str::<Value>(&line) {
+                Ok(message) => self.handle_message(&message),
+                Err(err) => Some(error_response(Value::Null, PARSE_ERROR, &err.to_string())),
+            };
+            if let Some(response) = response {
+                serde_json::to_writer(&mut writer, &response)?;
+                writer.write_all(b"\n")?;
+                writer.flush()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Serves over the process's stdin and stdout.
+    pub fn serve_stdio(&self) -> io::Result<()> {
+        self.serve(io::stdin().lock(), io::stdout().lock())
+    }
+
+    fn initialize(&self, params: &Value) -> Value {
+        let requested = params.get("protocolVersion").and_then(Value::as_str);
+        let version = requested
+            .filter(|v| SUPPORTED_PROTOCOL_VERSIONS.contains(v))
+            .unwrap_or(LATEST_PROTOCOL_VERSION);
+        json!({
+            "protocolVersion": version,
+            "capabilities": { "tools": { "listChanged": false } },
+            "serverInfo": { "name": self.name, "version": self.version },
+        })
+    }
+
+    fn call(&self, params: &Value) -> Result<Value, (i64, String)> {
+        let Some(name) = params.get("name").and_then(Value::as_str) else {
+            return Err((INVALID_PARAMS, "missing tool name".to_string()));
+        };
+        let Some(tool) = self.tools.iter().find(|t| t.name == name) else {
+            return Err((INVALID_PARAMS, format!("unknown tool: {name}")));
+        };
+        let empty = Value::Object(Map::new());
+        let arguments = params.get("arguments").unwrap_or(&empty);
+        Ok((tool.handler)(arguments))
+    }
+}
+
+fn success_result(output: Value) -> Value {
+    let text = output.to_string();
+    let mut result = json!({
+        "content": [{ "type": "text", "text": text }],
+        "isError": false,
+    });
+    if output.is_object() {
+        result["structuredContent"] = output;
+    }
+    result
+}
+
+fn error_result(message: String) -> Value {
+    json!({
+        "content": [{ "type": "text", "text": message }],
+        "isError": true,
+    })
+}
+
+fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message },
+    })
+}
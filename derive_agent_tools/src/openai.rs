@@ -0,0 +1,161 @@
+//! OpenAI function-tool definitions.
+//!
+//! [`AgentTool::openai_tool`](crate::AgentTool::openai_tool) renders the Chat
+//! Completions shape and
+//! [`AgentTool::openai_response_tool`](crate::AgentTool::openai_response_tool)
+//! the Responses API shape. With `strict = true` the parameters schema is
+//! passed through [`strict_schema`].
+
+use serde_json::{Map, Value};
+
+/// Rewrites a JSON Schema into the form OpenAI's structured outputs require
+/// for `strict: true`.
+///
+/// Every object gets `additionalProperties: false` and lists all of its
+/// properties as required. Properties that were optional become nullable
+/// instead, so the model can still omit a value by sending `null`. `oneOf` is
+/// rewritten to `anyOf`, which strict mode supports.
+///
+/// Nested schemas are rewritten too, including tuple positions and `allOf`
+/// entries. Some constructs cannot be expressed in strict mode at all and are
+/// left as they are, so OpenAI rejects a strict tool using them:
+///
+/// - maps (`additionalProperties` with a schema),
+/// - tuples (`prefixItems`),
+/// - untyped values such as `serde_json::Value` (`{}`),
+/// - several flattened enums, or an optional one (`allOf`),
+/// - a tool whose root is a union.
+///
+/// Use `strict = false` for such tools.
+pub fn strict_schema(mut schema: Value) -> Value {
+    make_strict(&mut schema);
+    schema
+}
+
+fn make_strict(schema: &mut Value) {
+    let Value::Object(map) = schema else {
+        return;
+    };
+
+    if let Some(branches) = map.remove("oneOf") {
+        map.insert("anyOf".to_string(), branches);
+    }
+    for key in ["anyOf", "allOf", "prefixItems"] {
+        if let Some(Value::Array(schemas)) = map.get_mut(key) {
+            schemas.iter_mut().for_each(make_strict);
+        }
+    }
+    if let Some(items) = map.get_mut("items") {
+        make_strict(items);
+    }
+
+    let required: Vec<String> = match map.get("required") {
+        Some(Value::Array(required)) => required
+            .iter()
+            .filter_map(|v| v.as_str().map(str::to_string))
+            .collect(),
+        _ => Vec::new(),
+    };
+    let Some(Value::Object(props)) = map.get_mut("properties") else {
+        return;
+    };
+    let mut all = Vec::with_capacity(props.len());
+    for (name, prop) in props.iter_mut() {
+        make_strict(prop);
+        if !required.contains(name) {
+            make_nullable(prop);
+        }
+        all.push(Value::String(name.clone()));
+    }
+    map.insert("required".to_string(), Value::Array(all));
+    if !map.contains_key("additionalProperties") {
+        map.insert("additionalProperties".to_string(), Value::Bool(false));
+    }
+}
+
+fn make_nullable(schema: &mut Value) {
+    let Value::Object(map) = schema else {
+        return;
+    };
+    match map.get_mut("type") {
+        Some(Value::String(typ)) => {
+            let typ = Value::String(std::mem::take(typ));
+            map.insert(
+                "type".to_string(),
+                Value::Array(vec![typ, Value::String("null".to_string())]),
+            );
+        }
+        Some(Value::Array(types)) => {
+            if !types.iter().any(|t| t == "null") {
+                types.push(Value::String("null".to_string()));
+            }
+        }
+        _ => {
+            let inner = Value::Object(std::mem::take(map));
+            let mut null = Map::new();
+            null.insert("type".to_string(), Value::String("null".to_string()));
+            map.insert(
+                "anyOf".to_string(),
+                Value::Array(vec![inner, Value::Object(null)]),
+            );
+            return;
+        }
+    }
+    if let Some(Value::Array(values)) = map.get_mut("enum") {
+        values.push(Value::Null);
+    }
+}
+
+/// `{"type": "function", "function": {...}}` for Chat Completions.
+pub(crate) fn chat_completions_tool(
+    name: &str,
+    description: Option<&str>,
+    parameters: Value,
+    strict: bool,
+) -> Value {
+    let mut tool = Map::new();
+    tool.insert("type".to_string(), Value::String("function".to_string()));
+    tool.insert(
+        "function".to_string(),
+        Value::Object(definition(name, description, parameters, strict)),
+    );
+    Value::Object(tool)
+}
+
+/// `{"type": "function", "name": ..., ...}` for the Responses API.
+pub(crate) fn responses_tool(
+    name: &str,
+    description: Option<&str>,
+    parameters: Value,
+    strict: bool,
+) -> Value {
+    let mut tool = definition(name, description, parameters, strict);
+    tool.insert("type".to_string(), Value::String("function".to_string()));
+    Value::Object(tool)
+}
+
+fn definition(
+    name: &str,
+    description: Option<&str>,
+    parameters: Value,
+    strict: bool,
+) -> Map<String, Value> {
+    let mut map = Map::new();
+    map.insert("name".to_string(), Value::String(name.to_string()));
+    if let Some(description) = description {
+        map.insert(
+            "description".to_string(),
+            Value::String(description.to_string()),
+        );
+    }
+    let parameters = if strict {
+        strict_schema(parameters)
+    } else {
+        parameters
+    };
+    map.insert("parameters".to_string(), parameters);
+    if strict {
+        map.insert("strict".to_string(), Value::Bool(true));
+    }
+    map
+}
@@ -0,0 +1,142 @@
+//! The [`AgentToolParameter`] trait for types nested inside tool inputs.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+
+use crate::schema::{Property, Schema, SchemaType};
+
+/// A type whose JSON Schema can be embedded in a tool's input schema.
+///
+/// Implemented by `#[derive(AgentToolParameter)]` and for common standard
+/// library types. Fields of an `AgentTool` whose type is not a primitive embed
+/// the schema returned here.
+pub trait AgentToolParameter {
+    /// Returns the schema for this type.
+    fn parameter_schema() -> Schema;
+
+    /// Returns the top-level object properties of this type's schema, or none
+    /// for other schemas. Unions keep their properties in their branches;
+    /// `#[serde(flatten)]` merges the whole [`parameter_schema`] instead.
+    ///
+    /// [`parameter_schema`]: AgentToolParameter::parameter_schema
+    fn parameter_properties() -> Vec<Property> {
+        Self::parameter_schema().properties.unwrap_or_default()
+    }
+
+    /// Returns the JSON Schema for this type in serde_json::Value form.
+    #[cfg(feature = "serde-json")]
+    fn parameter_schema_json() -> serde_json::Value {
+        Self::parameter_schema().to_json()
+    }
+
+    /// Returns the JSON Schema for this type as a Smithy `Document`.
+    #[cfg(feature = "bedrock")]
+    fn parameter_schema_document() -> aws_smithy_types::Document {
+        Self::parameter_schema().to_document()
+    }
+}
+
+macro_rules! impl_primitive {
+    ($typ:expr => $($ty:ty),+) => {$(
+        impl AgentToolParameter for $ty {
+            fn parameter_schema() -> Schema {
+                Schema::typed($typ)
+            }
+        }
+    )+};
+}
+
+impl_primitive!(SchemaType::Boolean => bool);
+impl_primitive!(SchemaType::Integer => i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+impl_primitive!(SchemaType::Number => f32, f64);
+impl_primitive!(SchemaType::String => String, str, char);
+
+impl<T: AgentToolParameter + ?Sized> AgentToolParameter for &T {
+    fn parameter_schema() -> Schema {
+        T::parameter_schema()
+    }
+}
+
+impl<T: AgentToolParameter + ?Sized> AgentToolParameter for Box<T> {
+    fn parameter_schema() -> Schema {
+        T::parameter_schema()
+    }
+}
+
+/// `Option<T>` uses `T`'s schema; requiredness is decided by the enclosing
+/// object.
+impl<T: AgentToolParameter> AgentToolParameter for Option<T> {
+    fn parameter_schema() -> Schema {
+        T::parameter_schema()
+    }
+}
+
+macro_rules! impl_sequence {
+    ($($ty:ident $(<$($extra:ident),*>)?),+) => {$(
+        impl<T: AgentToolParameter $($(, $extra)*)?> AgentToolParameter for $ty<T $($(, $extra)*)?> {
+            fn parameter_schema() -> Schema {
+                Schema::array(T::parameter_schema())
+            }
+        }
+    )+};
+}
+
+impl_sequence!(Vec, VecDeque, BTreeSet, HashSet<S>);
+
+impl<T: AgentToolParameter> AgentToolParameter for [T] {
+    fn parameter_schema() -> Schema {
+        Schema::array(T::parameter_schema())
+    }
+}
+
+impl<T: AgentToolParameter, const N: usize> AgentToolParameter for [T; N] {
+    fn parameter_schema() -> Schema {
+        let mut schema = Schema::array(T::parameter_schema());
+        schema.constraints.min_items = Some(N as u64);
+        schema.constraints.max_items = Some(N as u64);
+        schema
+    }
+}
+
+/// Tuples deserialize from arrays with one element per position.
+macro_rules! impl_tuple {
+    ($($len:literal => ($($name:ident),+)),+) => {$(
+        impl<$($name: AgentToolParameter),+> AgentToolParameter for ($($name,)+) {
+            fn parameter_schema() -> Schema {
+                let mut schema = Schema::typed(SchemaType::Array);
+                schema.prefix_items = Some(vec![$($name::parameter_schema()),+]);
+                schema.constraints.min_items = Some($len);
+                schema.constraints.max_items = Some($len);
+                schema
+            }
+        }
+    )+};
+}
+
+impl_tuple!(
+    1 => (A),
+    2 => (A, B),
+    3 => (A, B, C),
+    4 => (A, B, C, D),
+    5 => (A, B, C, D, E),
+    6 => (A, B, C, D, E, F)
+);
+
+impl<V: AgentToolParameter, S> AgentToolParameter for HashMap<String, V, S> {
+    fn parameter_schema() -> Schema {
+        Schema::map(V::parameter_schema())
+    }
+}
+
+impl<V: AgentToolParameter> AgentToolParameter for BTreeMap<String, V> {
+    fn parameter_schema() -> Schema {
+        Schema::map(V::parameter_schema())
+    }
+}
+
+/// Arbitrary JSON: an empty schema accepts any value.
+#[cfg(feature = "serde-json")]
+impl AgentToolParameter for serde_json::Value {
+    fn parameter_schema() -> Schema {
+        Schema::default()
+    }
+}
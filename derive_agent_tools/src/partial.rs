@@ -0,0 +1,279 @@
+//! Tolerant parsing of tool input that is still streaming in.
+//!
+//! [`parse_json`] reads a prefix of a JSON document, and
+//! [`PartialAgentTool::parse_partial`](crate::PartialAgentTool::parse_partial)
+//! turns it into the `<Tool>Partial` struct generated by `#[derive(AgentTool)]`,
+//! so a UI can show arguments before the final strict parse.
+
+use serde_json::{Map, Number, Value};
+
+use crate::AgentTool;
+
+/// A tool with a partial view of its input, generated by
+/// `#[derive(AgentTool)]` for structs as `<Tool>Partial`.
+///
+/// Every field of the partial struct is an `Option` that is `Some` once the
+/// field's value parses. Strings are shown truncated as they arrive; other
+/// values, including nested structs, appear once they are complete.
+///
+/// ```
+/// use derive_agent_tools::{AgentTool, PartialAgentTool};
+/// use serde::Deserialize;
+///
+/// #[derive(AgentTool, Deserialize)]
+/// struct WriteFile {
+///     #[tool(required)]
+///     path: String,
+///     #[tool(required)]
+///     content: String,
+/// }
+///
+/// let partial = WriteFile::parse_partial(r#"{"path": "notes.md", "content": "Dear di"#);
+/// assert_eq!(partial.path.as_deref(), Some("notes.md"));
+/// assert_eq!(partial.content.as_deref(), Some("Dear di"));
+/// ```
+pub trait PartialAgentTool: AgentTool {
+    /// The partial view of this tool's input.
+    type Partial: Default;
+
+    /// Builds the partial view from a (possibly incomplete) JSON input,
+    /// ignoring fields whose value does not parse.
+    fn partial_from_json(input: &Value) -> Self::Partial;
+
+    /// Builds the partial view from a prefix of the JSON input.
+    fn parse_partial(prefix: &str) -> Self::Partial {
+        match parse_json(prefix) {
+            Some(input) => Self::partial_from_json(&input),
+            None => Self::Partial::default(),
+        }
+    }
+}
+
+/// Parses a prefix of a JSON document, closing whatever is still open.
+///
+/// - Unterminated strings are kept with the characters received so far.
+/// - Open arrays and objects are closed; an object key without a value is
+///   dropped.
+/// - A number or `true`/`false`/`null` literal cut off by the end of the input
+///   is dropped, since its final value is not known yet.
+///
+/// Returns `None` when the prefix is empty or is not the start of valid JSON.
+///
+/// ```
+/// use derive_agent_tools::partial::parse_json;
+/// use serde_json::json;
+///
+/// assert_eq!(
+///     parse_json(r#"{"tags": ["a", "b"], "count": 1"#),
+///     Some(json!({ "tags": ["a", "b"] })),
+/// );
+/// assert_eq!(parse_json(r#"["fir"#), Some(json!(["fir"])));
+/// ```
+pub fn parse_json(prefix: &str) -> Option<Value> {
+    let mut parser = Parser {
+        input: prefix.as_bytes(),
+        pos: 0,
+    };
+    match parser.value() {
+        Ok(value) => Some(value),
+        Err(Stop::Eof(value)) => value,
+        Err(Stop::Invalid) => None,
+    }
+}
+
+/// Why parsing stopped before a value was complete.
+enum Stop {
+    /// The input ended; carries the partial value worth keeping, if any.
+    Eof(Option<Value>),
+    /// The input is not valid JSON.
+    Invalid,
+}
+
+struct Parser<'a> {
+    input: &'a [u8],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&mut self) -> Option<u8> {
+        while let Some(b) = self.input.get(self.pos) {
+            if !b.is_ascii_whitespace() {
+                return Some(*b);
+            }
+            self.pos += 1;
+        }
+        None
+    }
+
+    fn value(&mut self) -> Result<Value, Stop> {
+        match self.peek() {
+            None => Err(Stop::Eof(None)),
+            Some(b'{') => self.object(),
+            Some(b'[') => self.array(),
+            Some(b'"') => self.string().map(Value::String),
+            Some(b't') => self.literal("true", Value::Bool(true)),
+            Some(b'f') => self.literal("false", Value::Bool(false)),
+            Some(b'n') => self.literal("null", Value::Null),
+            Some(b'-' | b'0'..=b'9') => self.number(),
+            Some(_) => Err(Stop::Invalid),
+        }
+    }
+
+    fn object(&mut self) -> Result<Value, Stop> {
+        self.pos += 1;
+        let mut map = Map::new();
+        loop {
+            let eof = |map: Map<String, Value>| Stop::Eof(Some(Value::Object(map)));
+            match self.peek() {
+                None => return Err(eof(map)),
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(Value::Object(map));
+                }
+                Some(b',') if !map.is_empty() => {
+                    self.pos += 1;
+                    if self.peek().is_none() {
+                        return Err(eof(map));
+                    }
+                }
+                Some(_) if !map.is_empty() => return Err(Stop::Invalid),
+                Some(_) => {}
+            }
+            if self.peek() != Some(b'"') {
+                return Err(Stop::Invalid);
+            }
+            let key = match self.string() {
+                Ok(key) => key,
+                Err(Stop::Eof(_)) => return Err(eof(map)),
+                Err(stop) => return Err(stop),
+            };
+            match self.peek() {
+                None => return Err(eof(map)),
+                Some(b':') => self.pos += 1,
+                Some(_) => return Err(Stop::Invalid),
+            }
+            match self.value() {
+                Ok(value) => {
+                    map.insert(key, value);
+                }
+                Err(Stop::Eof(value)) => {
+                    if let Some(value) = value {
+                        map.insert(key, value);
+                    }
+                    return Err(eof(map));
+                }
+                Err(stop) => return Err(stop),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Value, Stop> {
+        self.pos += 1;
+        let mut items = Vec::new();
+        loop {
+            match self.peek() {
+                None => return Err(Stop::Eof(Some(Value::Array(items)))),
+                Some(b']') => {
+                    self.pos += 1;
+                    return Ok(Value::Array(items));
+                }
+                Some(b',') if !items.is_empty() => self.pos += 1,
+                Some(_) if !items.is_empty() => return Err(Stop::Invalid),
+                Some(_) => {}
+            }
+            match self.value() {
+                Ok(value) => items.push(value),
+                Err(Stop::Eof(value)) => {
+                    items.extend(value);
+                    return Err(Stop::Eof(Some(Value::Array(items))));
+                }
+                Err(stop) => return Err(stop),
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String, Stop> {
+        self.pos += 1;
+        let start = self.pos;
+        let mut escaped = false;
+        while let Some(&b) = self.input.get(self.pos) {
+            self.pos += 1;
+            match b {
+                _ if escaped => escaped = false,
+                b'\\' => escaped = true,
+                b'"' => {
+                    let raw = &self.input[start - 1..self.pos];
+                    return serde_json::from_slice(raw).map_err(|_| Stop::Invalid);
+                }
+                _ => {}
+            }
+        }
+        // The input ended inside the string: close it after the last complete
+        // character.
+        let raw = &self.input[start..];
+        let mut quoted = Vec::with_capacity(raw.len() + 2);
+        quoted.push(b'"');
+        quoted.extend_from_slice(&raw[..complete_prefix_len(raw)]);
+        quoted.push(b'"');
+        match serde_json::from_slice(&quoted) {
+            Ok(s) => Err(Stop::Eof(Some(Value::String(s)))),
+            Err(_) => Err(Stop::Invalid),
+        }
+    }
+
+    fn literal(&mut self, word: &str, value: Value) -> Result<Value, Stop> {
+        let rest = &self.input[self.pos..];
+        if rest.starts_with(word.as_bytes()) {
+            self.pos += word.len();
+            Ok(value)
+        } else if word.as_bytes().starts_with(rest) {
+            self.pos = self.input.len();
+            Err(Stop::Eof(None))
+        } else {
+            Err(Stop::Invalid)
+        }
+    }
+
+    fn number(&mut self) -> Result<Value, Stop> {
+        let start = self.pos;
+        while let Some(b) = self.input.get(self.pos) {
+            if !matches!(b, b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9') {
+                break;
+            }
+            self.pos += 1;
+        }
+        if self.pos == self.input.len() {
+            return Err(Stop::Eof(None));
+        }
+        serde_json::from_slice::<Number>(&self.input[start..self.pos])
+            .map(Value::Number)
+            .map_err(|_| Stop::Invalid)
+    }
+}
+
+/// Returns the length of the part of an unterminated string body that ends on
+/// a complete character, dropping a trailing escape sequence that was cut
+/// off (`\`, `\u00`) or a high surrogate still waiting for its pair.
+fn complete_prefix_len(raw: &[u8]) -> usize {
+    let mut i = 0;
+    while i < raw.len() {
+        if raw[i] != b'\\' {
+            i += 1;
+            continue;
+        }
+        let len = if raw.get(i + 1) == Some(&b'u') { 6 } else { 2 };
+        if i + len > raw.len() {
+            return i;
+        }
+        let high_surrogate = len == 6
+            && matches!(
+                &raw[i + 2..i + 4],
+                [b'd' | b'D', b'8'..=b'9' | b'a'..=b'b' | b'A'..=b'B']
+            );
+        if high_surrogate && i + len == raw.len() {
+            return i;
+        }
+        i += len;
+    }
+    raw.len()
+}
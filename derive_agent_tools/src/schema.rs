@@ -0,0 +1,492 @@
+//! Provider-independent model of a tool's input schema.
+//!
+//! The derives describe every tool and parameter as a [`Schema`], built once
+//! from static metadata. Providers render it into their own data model through
+//! [`SchemaValue`]; `serde_json::Value` and the Smithy `Document` used by
+//! Bedrock are supported out of the box.
+
+/// A JSON Schema, as produced by the derives and the built-in
+/// [`AgentToolParameter`](crate::AgentToolParameter) implementations.
+///
+/// Each field maps to one JSON Schema keyword and is omitted from the rendered
+/// schema when unset, so `Schema::default()` accepts any value.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Schema {
+    /// `"type"`
+    pub ty: Option<SchemaType>,
+    /// `"description"`
+    pub description: Option<String>,
+    /// `"properties"` and `"required"`
+    pub properties: Option<Vec<Property>>,
+    /// `"additionalProperties"`
+    pub additional_properties: Option<Box<Schema>>,
+    /// `"items"`
+    pub items: Option<Box<Schema>>,
+    /// `"prefixItems"`, one schema per position of a tuple.
+    pub prefix_items: Option<Vec<Schema>>,
+    /// `"enum"`, for string enums.
+    pub enum_values: Option<Vec<String>>,
+    /// `"oneOf"`
+    pub one_of: Option<Vec<Schema>>,
+    /// `"anyOf"`
+    pub any_of: Option<Vec<Schema>>,
+    /// `"allOf"`
+    pub all_of: Option<Vec<Schema>>,
+    /// `"$ref"`
+    pub reference: Option<String>,
+    /// Validation keywords.
+    pub constraints: Constraints,
+}
+
+/// The primitive JSON Schema types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchemaType {
+    Boolean,
+    Integer,
+    Number,
+    String,
+    Array,
+    Object,
+    Null,
+}
+
+/// A property of an object [`Schema`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Property {
+    pub name: String,
+    pub required: bool,
+    pub schema: Schema,
+}
+
+/// JSON Schema validation keywords; unset ones are not rendered.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Constraints {
+    /// `"minimum"`
+    pub minimum: Option<f64>,
+    /// `"maximum"`
+    pub maximum: Option<f64>,
+    /// `"minLength"`
+    pub min_length: Option<u64>,
+    /// `"maxLength"`
+    pub max_length: Option<u64>,
+    /// `"pattern"`
+    pub pattern: Option<String>,
+    /// `"format"`
+    pub format: Option<String>,
+    /// `"minItems"`
+    pub min_items: Option<u64>,
+    /// `"maxItems"`
+    pub max_items: Option<u64>,
+}
+
+impl SchemaType {
+    /// Returns the JSON Schema name of this type.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Boolean => "boolean",
+            Self::Integer => "integer",
+            Self::Number => "number",
+            Self::String => "string",
+            Self::Array => "array",
+            Self::Object => "object",
+            Self::Null => "null",
+        }
+    }
+}
+
+impl Schema {
+    /// `{"type": ty}`
+    pub fn typed(ty: SchemaType) -> Self {
+        Self {
+            ty: Some(ty),
+            ..Self::default()
+        }
+    }
+
+    /// `{"type": "array", "items": items}`
+    pub fn array(items: Schema) -> Self {
+        Self {
+            items: Some(Box::new(items)),
+            ..Self::typed(SchemaType::Array)
+        }
+    }
+
+    /// `{"type": "object", "properties": ..., "required": [...]}`
+    pub fn object(properties: Vec<Property>) -> Self {
+        Self {
+            properties: Some(properties),
+            ..Self::typed(SchemaType::Object)
+        }
+    }
+
+    /// `{"type": "object", "additionalProperties": values}`, for maps.
+    pub fn map(values: Schema) -> Self {
+        Self {
+            additional_properties: Some(Box::new(values)),
+            ..Self::typed(SchemaType::Object)
+        }
+    }
+
+    /// `{"type": "string", "enum": [...]}`
+    pub fn string_enum<I: IntoIterator<Item = S>, S: Into<String>>(values: I) -> Self {
+        Self {
+            enum_values: Some(values.into_iter().map(Into::into).collect()),
+            ..Self::typed(SchemaType::String)
+        }
+    }
+
+    /// `{"$ref": reference}`
+    pub fn reference(reference: impl Into<String>) -> Self {
+        Self {
+            reference: Some(reference.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Sets the description.
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Whether this schema is only a `oneOf` or an `anyOf`.
+    fn is_bare_union(&self) -> bool {
+        self.one_of.is_some() != self.any_of.is_some()
+            && *self
+                == Schema {
+                    one_of: self.one_of.clone(),
+                    any_of: self.any_of.clone(),
+                    ..Schema::default()
+                }
+    }
+
+    /// Renders this schema into any [`SchemaValue`] data model.
+    pub fn render<V: SchemaValue>(&self) -> V {
+        let mut entries = Vec::new();
+        let mut put = |key: &str, value: V| entries.push((key.to_string(), value));
+        let strings = |values: &[String]| V::array(values.iter().map(|v| V::string(v)).collect());
+        let schemas = |values: &[Schema]| V::array(values.iter().map(Schema::render).collect());
+
+        if let Some(reference) = &self.reference {
+            put("$ref", V::string(reference));
+        }
+        if let Some(ty) = self.ty {
+            put("type", V::string(ty.as_str()));
+        }
+        if let Some(description) = &self.description {
+            put("description", V::string(description));
+        }
+        if let Some(properties) = &self.properties {
+            let rendered = properties
+                .iter()
+                .map(|p| (p.name.clone(), p.schema.render()))
+                .collect();
+            put("properties", V::object(rendered));
+            let required: Vec<String> = properties
+                .iter()
+                .filter(|p| p.required)
+                .map(|p| p.name.clone())
+                .collect();
+            if !required.is_empty() {
+                put("required", strings(&required));
+            }
+        }
+        if let Some(values) = &self.additional_properties {
+            put("additionalProperties", values.render());
+        }
+        if let Some(items) = &self.items {
+            put("items", items.render());
+        }
+        if let Some(items) = &self.prefix_items {
+            put("prefixItems", schemas(items));
+        }
+        if let Some(values) = &self.enum_values {
+            put("enum", strings(values));
+        }
+        if let Some(branches) = &self.one_of {
+            put("oneOf", schemas(branches));
+        }
+        if let Some(branches) = &self.any_of {
+            put("anyOf", schemas(branches));
+        }
+        if let Some(parts) = &self.all_of {
+            put("allOf", schemas(parts));
+        }
+
+        let c = &self.constraints;
+        for (key, value) in [("minimum", c.minimum), ("maximum", c.maximum)] {
+            if let Some(value) = value {
+                put(key, V::number(value));
+            }
+        }
+        let counts = [
+            ("minLength", c.min_length),
+            ("maxLength", c.max_length),
+            ("minItems", c.min_items),
+            ("maxItems", c.max_items),
+        ];
+        for (key, value) in counts {
+            if let Some(value) = value {
+                put(key, V::unsigned(value));
+            }
+        }
+        if let Some(pattern) = &c.pattern {
+            put("pattern", V::string(pattern));
+        }
+        if let Some(format) = &c.format {
+            put("format", V::string(format));
+        }
+        V::object(entries)
+    }
+
+    /// Renders this schema as a `serde_json::Value`.
+    #[cfg(feature = "serde-json")]
+    pub fn to_json(&self) -> serde_json::Value {
+        self.render()
+    }
+
+    /// Renders this schema as a Smithy `Document`.
+    #[cfg(feature = "bedrock")]
+    pub fn to_document(&self) -> aws_smithy_types::Document {
+        self.render()
+    }
+}
+
+/// A JSON-like data model a [`Schema`] can be rendered into.
+///
+/// Implement it to target a new provider representation; see
+/// [`Schema::render`].
+pub trait SchemaValue: Sized {
+    fn string(value: &str) -> Self;
+    /// A number, rendered as an integer when `value` has no fractional part.
+    fn number(value: f64) -> Self;
+    fn unsigned(value: u64) -> Self;
+    fn array(items: Vec<Self>) -> Self;
+    fn object(entries: Vec<(String, Self)>) -> Self;
+}
+
+/// Whether `value` can be written as an `i64` without losing precision.
+#[cfg(any(feature = "serde-json", feature = "bedrock"))]
+fn is_integral(value: f64) -> bool {
+    value.fract() == 0.0 && value.abs() < i64::MAX as f64
+}
+
+#[cfg(feature = "serde-json")]
+impl SchemaValue for serde_json::Value {
+    fn string(value: &str) -> Self {
+        Self::String(value.to_string())
+    }
+
+    fn number(value: f64) -> Self {
+        if is_integral(value) {
+            Self::from(value as i64)
+        } else {
+            Self::from(value)
+        }
+    }
+
+    fn unsigned(value: u64) -> Self {
+        Self::from(value)
+    }
+
+    fn array(items: Vec<Self>) -> Self {
+        Self::Array(items)
+    }
+
+    fn object(entries: Vec<(String, Self)>) -> Self {
+        Self::Object(entries.into_iter().collect())
+    }
+}
+
+#[cfg(feature = "bedrock")]
+impl SchemaValue for aws_smithy_types::Document {
+    fn string(value: &str) -> Self {
+        Self::String(value.to_string())
+    }
+
+    fn number(value: f64) -> Self {
+        use aws_smithy_types::Number;
+
+        Self::Number(match value {
+            v if is_integral(v) && v < 0.0 => Number::NegInt(v as i64),
+            v if is_integral(v) => Number::PosInt(v as u64),
+            v => Number::Float(v),
+        })
+    }
+
+    fn unsigned(value: u64) -> Self {
+        Self::Number(aws_smithy_types::Number::PosInt(value))
+    }
+
+    fn array(items: Vec<Self>) -> Self {
+        Self::Array(items)
+    }
+
+    fn object(entries: Vec<(String, Self)>) -> Self {
+        Self::Object(entries.into_iter().collect())
+    }
+}
+
+// Static metadata emitted by the derives ------------------------------------
+
+/// Static description of a [`Schema`], emitted by the derives as a constant
+/// and turned into a `Schema` with [`SchemaMeta::build`].
+#[doc(hidden)]
+#[derive(Clone, Copy)]
+pub enum SchemaMeta {
+    Primitive(SchemaType),
+    Array(&'static SchemaMeta),
+    Object(&'static [PropertyMeta]),
+    StringEnum(&'static [&'static str]),
+    Null,
+    /// `oneOf` (`anyOf` with `any_of`), additionally typed as an object when
+    /// every branch is known to be one.
+    Union {
+        any_of: bool,
+        object: bool,
+        branches: &'static [BranchMeta],
+    },
+    /// `inner`'s object schema with a required string tag property prepended.
+    Tagged {
+        tag: &'static str,
+        value: &'static str,
+        inner: &'static SchemaMeta,
+    },
+    /// `AgentToolParameter::parameter_schema` of a nested type.
+    External(fn() -> Schema),
+}
+
+#[doc(hidden)]
+#[derive(Clone, Copy)]
+pub enum PropertyMeta {
+    Field {
+        name: &'static str,
+        description: Option<&'static str>,
+        required: bool,
+        schema: SchemaMeta,
+    },
+    /// `#[serde(flatten)]`: a nested type's schema merged into the object;
+    /// see [`SchemaMeta::build`].
+    Flatten {
+        schema: fn() -> Schema,
+        optional: bool,
+    },
+}
+
+#[doc(hidden)]
+#[derive(Clone, Copy)]
+pub struct BranchMeta {
+    pub description: Option<&'static str>,
+    pub schema: SchemaMeta,
+}
+
+impl SchemaMeta {
+    /// Builds the [`Schema`], with `description` attached when given.
+    ///
+    /// Flattened properties are merged into the object, none of them required
+    /// when the field is optional. Whatever else the flattened schema demands,
+    /// such as the branches of an enum, is kept next to the properties: as the
+    /// object's own `oneOf`/`anyOf` when it is a single union, in `allOf`
+    /// otherwise.
+    pub fn build(&self, description: Option<&str>) -> Schema {
+        let mut schema = match *self {
+            Self::Primitive(ty) => Schema::typed(ty),
+            Self::Array(items) => Schema::array(items.build(None)),
+            Self::Object(props) => {
+                let mut properties = Vec::new();
+                let mut additional_properties = None;
+                let mut rest = Vec::new();
+                for prop in props {
+                    match *prop {
+                        PropertyMeta::Field {
+                            name,
+                            description,
+                            required,
+                            schema,
+                        } => properties.push(Property {
+                            name: name.to_string(),
+                            required,
+                            schema: schema.build(description),
+                        }),
+                        PropertyMeta::Flatten { schema, optional } => {
+                            let mut nested = schema();
+                            let flattened = nested.properties.take().unwrap_or_default();
+                            properties.extend(flattened.into_iter().map(|p| Property {
+                                required: p.required && !optional,
+                                ..p
+                            }));
+                            // A flattened map collects the remaining keys.
+                            if additional_properties.is_none() {
+                                additional_properties = nested.additional_properties.take();
+                            }
+                            nested.ty = None;
+                            nested.description = None;
+                            if nested != Schema::default() {
+                                rest.push(if optional {
+                                    // serde falls back to `None` on any input.
+                                    Schema {
+                                        any_of: Some(vec![nested, Schema::default()]),
+                                        ..Schema::default()
+                                    }
+                                } else {
+                                    nested
+                                });
+                            }
+                        }
+                    }
+                }
+                let mut schema = Schema::object(properties);
+                schema.additional_properties = additional_properties;
+                if let [union] = rest.as_slice()
+                    && union.is_bare_union()
+                {
+                    let union = rest.remove(0);
+                    schema.one_of = union.one_of;
+                    schema.any_of = union.any_of;
+                } else if !rest.is_empty() {
+                    schema.all_of = Some(rest);
+                }
+                schema
+            }
+            Self::StringEnum(values) => Schema::string_enum(values.iter().copied()),
+            Self::Null => Schema::typed(SchemaType::Null),
+            Self::Union {
+                any_of,
+                object,
+                branches,
+            } => {
+                let branches = branches
+                    .iter()
+                    .map(|b| b.schema.build(b.description))
+                    .collect();
+                let mut schema = Schema {
+                    ty: object.then_some(SchemaType::Object),
+                    ..Schema::default()
+                };
+                if any_of {
+                    schema.any_of = Some(branches);
+                } else {
+                    schema.one_of = Some(branches);
+                }
+                schema
+            }
+            Self::Tagged { tag, value, inner } => {
+                let mut schema = inner.build(None);
+                schema.properties.get_or_insert_with(Vec::new).insert(
+                    0,
+                    Property {
+                        name: tag.to_string(),
+                        required: true,
+                        schema: Schema::string_enum([value]),
+                    },
+                );
+                schema
+            }
+            Self::External(schema) => schema(),
+        };
+        if let Some(description) = description {
+            schema.description = Some(description.to_string());
+        }
+        schema
+    }
+}
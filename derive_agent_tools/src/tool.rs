@@ -0,0 +1,325 @@
+//! The [`AgentTool`] trait implemented by `#[derive(AgentTool)]`.
+
+/// A tool that can be offered to a model and parsed back from its input.
+///
+/// Implemented by `#[derive(AgentTool)]`. Provider helpers are gated on the
+/// features enabled for this crate.
+pub trait AgentTool: Sized {
+    /// Error returned when a tool input cannot be parsed into `Self`.
+    type ParseError: std::error::Error + Send + Sync + 'static;
+
+    /// Returns the logical name of this tool.
+    fn tool_name() -> &'static str;
+
+    /// Returns the human-friendly description of this tool, if any.
+    fn tool_description() -> Option<&'static str>;
+
+    /// Returns a display title for this tool, if any.
+    fn tool_title() -> Option<&'static str> {
+        None
+    }
+
+    /// Returns behavioral hints about this tool.
+    fn tool_annotations() -> ToolAnnotations {
+        ToolAnnotations::default()
+    }
+
+    /// Returns the schema of this tool's input.
+    fn tool_schema() -> crate::Schema;
+
+    /// Returns the JSON Schema for this tool's input in serde_json::Value form.
+    #[cfg(feature = "serde-json")]
+    fn tool_schema_json() -> serde_json::Value {
+        Self::tool_schema().to_json()
+    }
+
+    /// Returns the JSON Schema for this tool's input as a Smithy `Document`.
+    #[cfg(feature = "bedrock")]
+    fn tool_schema_document() -> aws_smithy_types::Document {
+        Self::tool_schema().to_document()
+    }
+
+    /// Renders this tool's declaration in format `F`, e.g.
+    /// `WeatherTool::definition::<MyFormat>()`.
+    #[cfg(any(feature = "serde-json", feature = "bedrock"))]
+    fn definition<F: crate::format::ToolFormat>() -> F::Definition {
+        F::definition(crate::format::ToolInfo::of::<Self>())
+    }
+
+    /// Parses a call to this tool from format `F`'s call payload.
+    #[cfg(any(feature = "serde-json", feature = "bedrock"))]
+    fn parse_call<F: crate::format::ToolFormat>(call: &F::Call) -> Result<Self, F::Error>
+    where
+        Self: serde::de::DeserializeOwned,
+    {
+        F::parse_call(call)
+    }
+
+    /// Builds an AWS Bedrock ToolSpecification for this tool based on the schema.
+    #[cfg(feature = "bedrock")]
+    fn tool_spec() -> aws_sdk_bedrockruntime::types::ToolSpecification {
+        Self::definition::<crate::format::Bedrock>()
+    }
+
+    /// Builds the Bedrock `ToolResultBlock` answering a call to this tool.
+    ///
+    /// See [`bedrock::tool_result`](crate::bedrock::tool_result) for how the
+    /// output and errors are encoded.
+    #[cfg(all(feature = "bedrock", feature = "serde-json"))]
+    fn tool_result<O: serde::Serialize, E: std::fmt::Display>(
+        tool_use_id: impl Into<String>,
+        result: Result<O, E>,
+    ) -> aws_sdk_bedrockruntime::types::ToolResultBlock {
+        crate::bedrock::tool_result(tool_use_id, result)
+    }
+
+    /// Builds an OpenAI Chat Completions function tool,
+    /// `{"type": "function", "function": {"name", "description", "parameters"}}`.
+    ///
+    /// With `strict` the definition sets `strict: true` and the parameters
+    /// schema is rewritten by [`openai::strict_schema`](crate::openai::strict_schema).
+    #[cfg(feature = "openai")]
+    fn openai_tool(strict: bool) -> serde_json::Value {
+        crate::openai::chat_completions_tool(
+            Self::tool_name(),
+            Self::tool_description(),
+            Self::tool_schema_json(),
+            strict,
+        )
+    }
+
+    /// Builds an OpenAI Responses API function tool,
+    /// `{"type": "function", "name", "description", "parameters"}`.
+    ///
+    /// `strict` behaves as for [`AgentTool::openai_tool`].
+    #[cfg(feature = "openai")]
+    fn openai_response_tool(strict: bool) -> serde_json::Value {
+        crate::openai::responses_tool(
+            Self::tool_name(),
+            Self::tool_description(),
+            Self::tool_schema_json(),
+            strict,
+        )
+    }
+
+    /// Builds an Anthropic Messages API tool definition,
+    /// `{"name", "description", "input_schema"}`.
+    #[cfg(feature = "anthropic")]
+    fn anthropic_tool() -> serde_json::Value {
+        crate::anthropic::tool_definition(
+            Self::tool_name(),
+            Self::tool_description(),
+            Self::tool_schema_json(),
+        )
+    }
+
+    /// Parses an Anthropic `tool_use` content block into this tool, checking
+    /// that the block calls this tool by name.
+    #[cfg(feature = "anthropic")]
+    fn from_anthropic_tool_use(
+        block: &crate::anthropic::ToolUse,
+    ) -> Result<Self, crate::anthropic::ToolUseError>
+    where
+        Self: serde::de::DeserializeOwned,
+    {
+        if block.name != Self::tool_name() {
+            return Err(crate::anthropic::ToolUseError::NameMismatch {
+                expected: Self::tool_name(),
+                found: block.name.clone(),
+            });
+        }
+        Self::deserialize(&block.input).map_err(crate::anthropic::ToolUseError::InvalidInput)
+    }
+
+    /// Builds a Gemini `functionDeclarations` entry,
+    /// `{"name", "description", "parameters"}`, with the parameters schema
+    /// converted by [`gemini::gemini_schema`](crate::gemini::gemini_schema).
+    #[cfg(feature = "gemini")]
+    fn gemini_function_declaration() -> serde_json::Value {
+        crate::gemini::function_declaration(
+            Self::tool_name(),
+            Self::tool_description(),
+            &Self::tool_schema_json(),
+        )
+    }
+
+    /// Builds a Model Context Protocol `Tool` descriptor,
+    /// `{"name", "title", "description", "inputSchema", "annotations"}`.
+    #[cfg(feature = "mcp")]
+    fn mcp_tool() -> serde_json::Value {
+        crate::mcp::tool_descriptor(
+            Self::tool_name(),
+            Self::tool_title(),
+            Self::tool_description(),
+            Self::tool_schema_json(),
+            Self::tool_annotations(),
+        )
+    }
+
+    /// Parses a Bedrock tool input `Document` into this tool.
+    ///
+    /// The default deserializes it with [`document::from_document`]; it has a
+    /// body so that enabling `bedrock` never breaks an implementation written
+    /// without it.
+    ///
+    /// [`document::from_document`]: crate::document::from_document
+    #[cfg(feature = "bedrock")]
+    fn from_document(input: &aws_smithy_types::Document) -> Result<Self, Self::ParseError>
+    where
+        Self: serde::de::DeserializeOwned,
+        Self::ParseError: From<crate::document::Error>,
+    {
+        crate::document::from_document(input).map_err(Into::into)
+    }
+
+    /// Parses a Bedrock `ToolUseBlock` into this tool, checking that the block
+    /// calls this tool by name and keeping its `tool_use_id` for the reply.
+    #[cfg(feature = "bedrock")]
+    fn from_tool_use(
+        block: &aws_sdk_bedrockruntime::types::ToolUseBlock,
+    ) -> Result<crate::bedrock::ToolInvocation<Self>, crate::bedrock::ToolUseError>
+    where
+        Self: serde::de::DeserializeOwned,
+    {
+        if block.name() != Self::tool_name() {
+            return Err(crate::bedrock::ToolUseError::NameMismatch {
+                tool_use_id: block.tool_use_id().to_string(),
+                expected: Self::tool_name(),
+                found: block.name().to_string(),
+            });
+        }
+        let input = crate::document::from_document(block.input()).map_err(|err| {
+            crate::bedrock::ToolUseError::InvalidInput {
+                tool_use_id: block.tool_use_id().to_string(),
+                source: Box::new(err),
+            }
+        })?;
+        Ok(crate::bedrock::ToolInvocation {
+            tool_use_id: block.tool_use_id().to_string(),
+            input,
+        })
+    }
+}
+
+/// Hints describing a tool's behavior, set with struct-level
+/// `#[tool(read_only, destructive, idempotent, open_world)]`.
+///
+/// Hints are advisory: clients must not rely on them for safety. `None` means
+/// the hint was not given.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ToolAnnotations {
+    /// The tool does not modify its environment.
+    pub read_only: Option<bool>,
+    /// The tool may perform destructive updates.
+    pub destructive: Option<bool>,
+    /// Calling the tool repeatedly with the same input has no additional effect.
+    pub idempotent: Option<bool>,
+    /// The tool interacts with an open world of external entities.
+    pub open_world: Option<bool>,
+}
+
+/// Object-safe view of an [`AgentTool`] type.
+///
+/// `AgentTool` only has associated functions, so it cannot be used behind
+/// `dyn`. [`ToolType`] implements this trait for any `AgentTool`, which allows
+/// heterogeneous tools to be stored as `Box<dyn DynAgentTool>`.
+pub trait DynAgentTool {
+    /// See [`AgentTool::tool_name`].
+    fn name(&self) -> &'static str;
+
+    /// See [`AgentTool::tool_description`].
+    fn description(&self) -> Option<&'static str>;
+
+    /// See [`AgentTool::tool_schema_json`].
+    #[cfg(feature = "serde-json")]
+    fn schema_json(&self) -> serde_json::Value;
+
+    /// See [`AgentTool::tool_spec`].
+    #[cfg(feature = "bedrock")]
+    fn spec(&self) -> aws_sdk_bedrockruntime::types::ToolSpecification;
+}
+
+/// The tool type `T` as a value, without an instance of its input.
+///
+/// ```
+/// use derive_agent_tools::{AgentTool, DynAgentTool, ToolType};
+/// use serde::Deserialize;
+///
+/// #[derive(AgentTool, Deserialize)]
+/// struct PingTool {
+///     host: String,
+/// }
+///
+/// let tools: Vec<Box<dyn DynAgentTool>> = vec![Box::new(ToolType::<PingTool>::new())];
+/// assert_eq!(tools[0].name(), "PingTool");
+/// ```
+pub struct ToolType<T>(std::marker::PhantomData<fn() -> T>);
+
+impl<T: AgentTool> ToolType<T> {
+    /// Creates the descriptor of tool `T`.
+    pub const fn new() -> Self {
+        Self(std::marker::PhantomData)
+    }
+}
+
+impl<T: AgentTool> Default for ToolType<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for ToolType<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for ToolType<T> {}
+
+impl<T> std::fmt::Debug for ToolType<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("ToolType")
+            .field(&std::any::type_name::<T>())
+            .finish()
+    }
+}
+
+impl<T: AgentTool> DynAgentTool for ToolType<T> {
+    fn name(&self) -> &'static str {
+        T::tool_name()
+    }
+
+    fn description(&self) -> Option<&'static str> {
+        T::tool_description()
+    }
+
+    #[cfg(feature = "serde-json")]
+    fn schema_json(&self) -> serde_json::Value {
+        T::tool_schema_json()
+    }
+
+    #[cfg(feature = "bedrock")]
+    fn spec(&self) -> aws_sdk_bedrockruntime::types::ToolSpecification {
+        T::tool_spec()
+    }
+}
+
+/// Error returned by derived [`AgentTool::from_document`] implementations,
+/// carrying the deserializer's message.
+#[derive(Debug, Clone)]
+pub struct ToolParseError(pub String);
+
+impl std::fmt::Display for ToolParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for ToolParseError {}
+
+#[cfg(feature = "bedrock")]
+impl From<crate::document::Error> for ToolParseError {
+    fn from(err: crate::document::Error) -> Self {
+        Self(err.to_string())
+    }
+}
@@ -0,0 +1,197 @@
+//! [`ToolBox`], a registry that dispatches tool calls by name.
+
+use std::fmt;
+use std::sync::Arc;
+
+use serde::de::DeserializeOwned;
+
+use crate::AgentTool;
+
+type Dispatch<I, R> = Box<dyn Fn(&I) -> Result<R, ToolBoxError> + Send + Sync>;
+
+struct Entry<R> {
+    name: &'static str,
+    schema_json: fn() -> serde_json::Value,
+    #[cfg(feature = "bedrock")]
+    tool_spec: fn() -> aws_sdk_bedrockruntime::types::ToolSpecification,
+    call_json: Dispatch<serde_json::Value, R>,
+    #[cfg(feature = "bedrock")]
+    call_document: Dispatch<aws_smithy_types::Document, R>,
+}
+
+/// A set of tools with a handler each, replacing the hand-written
+/// `match name { ... }` of an agent loop.
+///
+/// Every handler returns the same `R`, typically a `Result` or a
+/// `serde_json::Value` to send back to the model.
+///
+/// ```
+/// use derive_agent_tools::{AgentTool, ToolBox};
+/// use serde::Deserialize;
+///
+/// #[derive(AgentTool, Deserialize)]
+/// struct WeatherTool {
+///     #[tool(required)]
+///     city: String,
+/// }
+///
+/// let mut tools = ToolBox::new();
+/// tools.register(|args: WeatherTool| format!("Sunny in {}", args.city));
+///
+/// let input = serde_json::json!({ "city": "Paris" });
+/// assert_eq!(tools.call_json("WeatherTool", &input).unwrap(), "Sunny in Paris");
+/// ```
+pub struct ToolBox<R> {
+    entries: Vec<Entry<R>>,
+}
+
+impl<R> ToolBox<R> {
+    /// Creates an empty tool box.
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    /// Registers tool `T` with the handler invoked for its calls.
+    ///
+    /// Registering a tool name twice replaces the earlier handler.
+    pub fn register<T, F>(&mut self, handler: F) -> &mut Self
+    where
+        T: AgentTool + DeserializeOwned + 'static,
+        F: Fn(T) -> R + Send + Sync + 'static,
+    {
+        let handler = Arc::new(handler);
+        #[cfg(feature = "bedrock")]
+        let call_document: Dispatch<aws_smithy_types::Document, R> = {
+            let handler = Arc::clone(&handler);
+            Box::new(move |input| {
+                let args = crate::document::from_document(input).map_err(|err| {
+                    ToolBoxError::InvalidInput {
+                        tool: T::tool_name(),
+                        source: Box::new(err),
+                    }
+                })?;
+                Ok(handler(args))
+            })
+        };
+        let call_json: Dispatch<serde_json::Value, R> = Box::new(move |input| {
+            let args = T::deserialize(input).map_err(|err| ToolBoxError::InvalidInput {
+                tool: T::tool_name(),
+                source: Box::new(err),
+            })?;
+            Ok(handler(args))
+        });
+
+        self.entries.retain(|entry| entry.name != T::tool_name());
+        self.entries.push(Entry {
+            name: T::tool_name(),
+            schema_json: T::tool_schema_json,
+            #[cfg(feature = "bedrock")]
+            tool_spec: T::tool_spec,
+            call_json,
+            #[cfg(feature = "bedrock")]
+            call_document,
+        });
+        self
+    }
+
+    /// Returns the names of the registered tools, in registration order.
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.entries.iter().map(|entry| entry.name)
+    }
+
+    /// Returns `(name, input schema)` for every registered tool.
+    pub fn json_schemas(&self) -> Vec<(&'static str, serde_json::Value)> {
+        self.entries
+            .iter()
+            .map(|entry| (entry.name, (entry.schema_json)()))
+            .collect()
+    }
+
+    /// Builds a Bedrock `ToolConfiguration` offering every registered tool.
+    #[cfg(feature = "bedrock")]
+    pub fn tool_configuration(&self) -> aws_sdk_bedrockruntime::types::ToolConfiguration {
+        let tools = self
+            .entries
+            .iter()
+            .map(|entry| aws_sdk_bedrockruntime::types::Tool::ToolSpec((entry.tool_spec)()))
+            .collect();
+        aws_sdk_bedrockruntime::types::ToolConfiguration::builder()
+            .set_tools(Some(tools))
+            .build()
+            .expect("valid ToolConfiguration")
+    }
+
+    /// Parses a JSON tool input for tool `name` and invokes its handler.
+    pub fn call_json(&self, name: &str, input: &serde_json::Value) -> Result<R, ToolBoxError> {
+        (self.entry(name)?.call_json)(input)
+    }
+
+    /// Parses a Bedrock tool input `Document` for tool `name` and invokes its
+    /// handler.
+    #[cfg(feature = "bedrock")]
+    pub fn call_document(
+        &self,
+        name: &str,
+        input: &aws_smithy_types::Document,
+    ) -> Result<R, ToolBoxError> {
+        (self.entry(name)?.call_document)(input)
+    }
+
+    fn entry(&self, name: &str) -> Result<&Entry<R>, ToolBoxError> {
+        self.entries
+            .iter()
+            .find(|entry| entry.name == name)
+            .ok_or_else(|| ToolBoxError::UnknownTool(name.to_string()))
+    }
+}
+
+impl<R> Default for ToolBox<R> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<R> fmt::Debug for ToolBox<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ToolBox")
+            .field(
+                "tools",
+                &self.entries.iter().map(|e| e.name).collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+/// Error returned when a [`ToolBox`] cannot dispatch a tool call.
+#[derive(Debug)]
+pub enum ToolBoxError {
+    /// No tool with this name is registered.
+    UnknownTool(String),
+    /// The input does not parse into the tool.
+    InvalidInput {
+        tool: &'static str,
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+}
+
+impl fmt::Display for ToolBoxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownTool(name) => write!(f, "unknown tool `{name}`"),
+            Self::InvalidInput { tool, source } => {
+                write!(f, "invalid input for tool `{tool}`: {source}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ToolBoxError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::UnknownTool(_) => None,
+            Self::InvalidInput { source, .. } => Some(source.as_ref()),
+        }
+    }
+}
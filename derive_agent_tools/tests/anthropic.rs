@@ -0,0 +1,72 @@
+#![cfg(feature = "anthropic")]
+
+use derive_agent_tools::AgentTool;
+use derive_agent_tools::anthropic::{ToolUse, ToolUseError};
+use serde::Deserialize;
+use serde_json::json;
+
+#[derive(Debug, PartialEq, AgentTool, Deserialize)]
+#[tool(name = "get_weather", description = "Get the weather")]
+struct WeatherTool {
+    #[tool(required)]
+    city: String,
+    days: Option<u8>,
+}
+
+#[test]
+fn tool_definition_shape() {
+    assert_eq!(
+        WeatherTool::anthropic_tool(),
+        json!({
+            "name": "get_weather",
+            "description": "Get the weather",
+            "input_schema": WeatherTool::tool_schema_json(),
+        })
+    );
+}
+
+#[test]
+fn parses_tool_use_blocks() {
+    let block: ToolUse = serde_json::from_value(json!({
+        "type": "tool_use",
+        "id": "toolu_01",
+        "name": "get_weather",
+        "input": { "city": "Paris", "days": 3 },
+    }))
+    .expect("tool_use block");
+    assert_eq!(block.id, "toolu_01");
+
+    let parsed = WeatherTool::from_anthropic_tool_use(&block).expect("parseable");
+    assert_eq!(
+        parsed,
+        WeatherTool {
+            city: "Paris".to_string(),
+            days: Some(3)
+        }
+    );
+}
+
+#[test]
+fn rejects_other_tools_and_bad_input() {
+    let other = ToolUse {
+        id: "toolu_02".to_string(),
+        name: "get_time".to_string(),
+        input: json!({}),
+    };
+    assert!(matches!(
+        WeatherTool::from_anthropic_tool_use(&other),
+        Err(ToolUseError::NameMismatch {
+            expected: "get_weather",
+            ref found,
+        }) if found == "get_time"
+    ));
+
+    let bad = ToolUse {
+        id: "toolu_03".to_string(),
+        name: "get_weather".to_string(),
+        input: json!({ "days": 3 }),
+    };
+    let err = WeatherTool::from_anthropic_tool_use(&bad).unwrap_err();
+    assert!(matches!(err, ToolUseError::InvalidInput(_)));
+    assert!(err.to_string().contains("city"));
+}
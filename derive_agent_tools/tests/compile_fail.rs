@@ -0,0 +1,5 @@
+#[test]
+fn tool_attribute_errors() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
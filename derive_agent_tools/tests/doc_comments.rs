@@ -0,0 +1,115 @@
+use derive_agent_tools::{AgentTool, AgentToolParameter};
+use serde::Deserialize;
+use serde_json::json;
+
+/// Search the **order** history.
+///
+/// Results are sorted by `date`,
+/// newest first.
+#[derive(AgentTool, Deserialize)]
+#[allow(dead_code)]
+struct SearchOrders {
+    /// Free-text query.
+    #[tool(required)]
+    query: String,
+    /// Ignored in favor of the explicit description.
+    #[tool(description = "Maximum number of results")]
+    limit: Option<u32>,
+    status: Option<Status>,
+}
+
+/// Order status.
+#[derive(AgentToolParameter, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[allow(dead_code)]
+enum Status {
+    /// Not shipped yet
+    Open,
+    /// Delivered to the customer
+    Shipped,
+}
+
+#[test]
+fn doc_comments_are_fallback_descriptions() {
+    assert_eq!(
+        SearchOrders::tool_description(),
+        Some("Search the **order** history.\n\nResults are sorted by `date`,\nnewest first.")
+    );
+    assert_eq!(
+        SearchOrders::tool_schema_json(),
+        json!({
+            "type": "object",
+            "properties": {
+                "query": { "type": "string", "description": "Free-text query." },
+                "limit": { "type": "integer", "description": "Maximum number of results" },
+                "status": {
+                    "type": "string",
+                    "enum": ["open", "shipped"],
+                    "description": "Order status.\n\n- `open`: Not shipped yet\n- `shipped`: Delivered to the customer",
+                },
+            },
+            "required": ["query"],
+        })
+    );
+}
+
+/// Cancel an order.
+/// Cannot be *undone*.
+#[derive(AgentTool, Deserialize)]
+#[tool(strip_markdown)]
+#[allow(dead_code)]
+struct CancelOrder {
+    /// The order's `id`, see [`SearchOrders`] or the
+    /// [dashboard](https://example.com/orders).
+    ///
+    /// ```text
+    /// ord_123
+    /// ```
+    #[tool(required)]
+    id: String,
+    /// Why the order is cancelled:
+    ///
+    /// - _customer_ request
+    /// - *fraud*
+    reason: Option<String>,
+}
+
+#[test]
+fn markdown_can_be_stripped() {
+    assert_eq!(
+        CancelOrder::tool_description(),
+        Some("Cancel an order. Cannot be undone.")
+    );
+    assert_eq!(
+        CancelOrder::tool_schema_json()["properties"],
+        json!({
+            "id": {
+                "type": "string",
+                "description": "The order's id, see SearchOrders or the dashboard.\n\nord_123",
+            },
+            "reason": {
+                "type": "string",
+                "description": "Why the order is cancelled:\n\n- _customer_ request\n- fraud",
+            },
+        })
+    );
+}
+
+#[derive(AgentTool, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+#[allow(dead_code)]
+enum Inventory {
+    /// Add stock
+    Restock { quantity: u32 },
+    /// Remove stock
+    Remove { quantity: u32 },
+}
+
+#[test]
+fn variant_doc_comments_describe_branches() {
+    let schema = Inventory::tool_schema_json();
+    let branches = schema["oneOf"].as_array().expect("oneOf");
+    assert_eq!(branches[0]["description"], "Add stock");
+    assert_eq!(branches[1]["description"], "Remove stock");
+    assert_eq!(Inventory::tool_description(), None);
+}
@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 use aws_smithy_types::{Document, Number};
 use derive_agent_tools::AgentTool;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, PartialEq, AgentTool, Deserialize)]
 #[tool(description = "Doc round trip")]
@@ -14,7 +14,7 @@ struct RoundTrip {
 }
 
 #[test]
-#[cfg(all(feature = "bedrock", feature = "serde-json"))]
+#[cfg(feature = "bedrock")]
 fn parses_document_payloads() {
     let mut inner = HashMap::new();
     inner.insert("answer".to_string(), Document::Number(Number::PosInt(42)));
@@ -30,3 +30,142 @@ fn parses_document_payloads() {
         }
     );
 }
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum Shape {
+    Dot,
+    Circle { radius: f64 },
+    Label(String),
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Scene<'a> {
+    name: &'a str,
+    shapes: Vec<Shape>,
+    offset: (i64, u8),
+    note: Option<String>,
+}
+
+#[test]
+#[cfg(feature = "bedrock")]
+fn deserializes_without_json() {
+    use derive_agent_tools::document::from_document;
+
+    let object = |entries: Vec<(&str, Document)>| {
+        Document::Object(
+            entries
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v))
+                .collect(),
+        )
+    };
+    let doc = object(vec![
+        ("name", Document::String("demo".to_string())),
+        (
+            "shapes",
+            Document::Array(vec![
+                Document::String("Dot".to_string()),
+                object(vec![(
+                    "Circle",
+                    object(vec![("radius", Document::Number(Number::Float(2.5)))]),
+                )]),
+                object(vec![("Label", Document::String("hi".to_string()))]),
+            ]),
+        ),
+        (
+            "offset",
+            Document::Array(vec![
+                Document::Number(Number::NegInt(-3)),
+                Document::Number(Number::PosInt(7)),
+            ]),
+        ),
+        ("note", Document::Null),
+        ("ignored", Document::Bool(false)),
+    ]);
+
+    let scene: Scene<'_> = from_document(&doc).expect("parseable");
+
+    // Serializing reproduces the document, minus the unknown field.
+    let mut expected = doc.clone();
+    if let Document::Object(map) = &mut expected {
+        map.remove("ignored");
+    }
+    assert_eq!(
+        derive_agent_tools::document::to_document(&scene).unwrap(),
+        expected
+    );
+
+    assert_eq!(
+        scene,
+        Scene {
+            name: "demo",
+            shapes: vec![
+                Shape::Dot,
+                Shape::Circle { radius: 2.5 },
+                Shape::Label("hi".to_string())
+            ],
+            offset: (-3, 7),
+            note: None,
+        }
+    );
+
+    let keyed: HashMap<u32, String> =
+        from_document(&object(vec![("7", Document::String("a".to_string()))])).unwrap();
+    assert_eq!(keyed, HashMap::from([(7, "a".to_string())]));
+    let err =
+        from_document::<HashMap<u32, String>>(&object(vec![("x", Document::Null)])).unwrap_err();
+    assert_eq!(err.to_string(), "invalid type: string \"x\", expected u32");
+
+    let three = Document::Array(vec![Document::Number(Number::PosInt(1)); 3]);
+    let err = from_document::<(i32, i32)>(&three).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid length 3, expected fewer elements in array"
+    );
+    assert!(from_document::<[u8; 2]>(&three).is_err());
+
+    let err = from_document::<u8>(&Document::Number(Number::PosInt(300))).unwrap_err();
+    assert!(err.to_string().contains("300"), "{err}");
+    let err = from_document::<RoundTrip>(&object(vec![])).unwrap_err();
+    assert_eq!(err.to_string(), "missing field `answer`");
+}
+
+#[test]
+#[cfg(feature = "bedrock")]
+fn serializes_numbers_and_map_keys() {
+    use std::collections::BTreeMap;
+
+    use derive_agent_tools::document::to_document;
+
+    assert_eq!(
+        to_document(&0i32).unwrap(),
+        Document::Number(Number::PosInt(0))
+    );
+    assert_eq!(
+        to_document(&i64::MIN).unwrap(),
+        Document::Number(Number::NegInt(i64::MIN))
+    );
+    assert_eq!(
+        to_document(&u128::from(u64::MAX)).unwrap(),
+        Document::Number(Number::PosInt(u64::MAX))
+    );
+    assert!(to_document(&i128::MIN).is_err());
+    assert_eq!(
+        to_document(&1.5f32).unwrap(),
+        Document::Number(Number::Float(1.5))
+    );
+
+    let keyed = BTreeMap::from([(1u8, 'a'), (2, 'b')]);
+    assert_eq!(
+        to_document(&keyed).unwrap(),
+        Document::Object(HashMap::from([
+            ("1".to_string(), Document::String("a".to_string())),
+            ("2".to_string(), Document::String("b".to_string())),
+        ]))
+    );
+    let keys: BTreeMap<u8, char> =
+        derive_agent_tools::document::from_document(&to_document(&keyed).unwrap()).unwrap();
+    assert_eq!(keys, keyed);
+    let err = to_document(&HashMap::from([((1, 2), 3)])).unwrap_err();
+    assert_eq!(err.to_string(), "map key must be a string");
+}
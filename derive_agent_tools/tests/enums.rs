@@ -0,0 +1,91 @@
+use derive_agent_tools::{AgentTool, AgentToolParameter};
+use serde::Deserialize;
+use serde_json::json;
+
+#[allow(dead_code)]
+#[derive(AgentToolParameter, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Unit {
+    Celsius,
+    Fahrenheit,
+    #[serde(rename = "K")]
+    Kelvin,
+}
+
+#[allow(dead_code)]
+#[derive(AgentToolParameter, Deserialize)]
+#[tool(description = "Sort order")]
+#[serde(rename_all = "SCREAMING-KEBAB-CASE")]
+enum Order {
+    #[tool(description = "Smallest first")]
+    LowToHigh,
+    #[tool(description = "Largest first")]
+    HighToLow,
+    #[serde(skip)]
+    Unsorted,
+    #[serde(other)]
+    Unknown,
+}
+
+#[allow(dead_code)]
+#[derive(AgentTool, Deserialize)]
+struct ForecastTool {
+    #[tool(required, description = "Temperature unit")]
+    unit: Unit,
+    order: Option<Order>,
+}
+
+#[test]
+fn unit_enums_become_string_sets() {
+    assert_eq!(
+        Unit::parameter_schema_json(),
+        json!({ "type": "string", "enum": ["celsius", "fahrenheit", "K"] })
+    );
+}
+
+#[test]
+fn variant_descriptions_are_listed() {
+    assert_eq!(
+        Order::parameter_schema_json(),
+        json!({
+            "type": "string",
+            "description": "Sort order\n\n- `LOW-TO-HIGH`: Smallest first\n- `HIGH-TO-LOW`: Largest first",
+            "enum": ["LOW-TO-HIGH", "HIGH-TO-LOW"],
+        })
+    );
+}
+
+#[test]
+fn enum_fields_embed_the_value_set() {
+    let schema = ForecastTool::tool_schema_json();
+    assert_eq!(
+        schema["properties"]["unit"],
+        json!({
+            "type": "string",
+            "description": "Temperature unit",
+            "enum": ["celsius", "fahrenheit", "K"],
+        })
+    );
+    assert_eq!(
+        schema["properties"]["order"]["enum"],
+        json!(["LOW-TO-HIGH", "HIGH-TO-LOW"])
+    );
+}
+
+#[test]
+#[cfg(feature = "bedrock")]
+fn enum_document_schema() {
+    use aws_smithy_types::Document;
+
+    let Document::Object(map) = Unit::parameter_schema_document() else {
+        panic!("expected an object schema");
+    };
+    assert_eq!(
+        map.get("enum"),
+        Some(&Document::Array(vec![
+            Document::String("celsius".to_string()),
+            Document::String("fahrenheit".to_string()),
+            Document::String("K".to_string()),
+        ]))
+    );
+}
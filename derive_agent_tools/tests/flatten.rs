@@ -0,0 +1,191 @@
+use derive_agent_tools::{AgentTool, AgentToolParameter, PartialAgentTool};
+use serde::Deserialize;
+use serde_json::json;
+
+#[derive(Debug, PartialEq, AgentToolParameter, Deserialize)]
+struct Pagination {
+    #[tool(required, description = "1-based page")]
+    page: u32,
+    page_size: Option<u32>,
+}
+
+#[derive(Debug, PartialEq, AgentToolParameter, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AuthContext {
+    #[tool(required)]
+    api_key: String,
+}
+
+#[derive(Debug, PartialEq, AgentTool, Deserialize)]
+struct ListOrders {
+    #[tool(required)]
+    customer: String,
+    #[serde(flatten)]
+    pagination: Pagination,
+    #[serde(flatten)]
+    auth: Option<AuthContext>,
+}
+
+#[test]
+fn flattened_properties_are_merged() {
+    assert_eq!(
+        ListOrders::tool_schema_json(),
+        json!({
+            "type": "object",
+            "properties": {
+                "customer": { "type": "string" },
+                "page": { "type": "integer", "description": "1-based page" },
+                "page_size": { "type": "integer" },
+                "apiKey": { "type": "string" },
+            },
+            "required": ["customer", "page"],
+        })
+    );
+
+    let names: Vec<_> = Pagination::parameter_properties()
+        .into_iter()
+        .map(|p| (p.name, p.required))
+        .collect();
+    assert_eq!(
+        names,
+        [("page".to_string(), true), ("page_size".to_string(), false)]
+    );
+}
+
+#[test]
+#[cfg(feature = "bedrock")]
+fn flattened_properties_are_merged_in_documents() {
+    use aws_smithy_types::Document;
+
+    let Document::Object(schema) = ListOrders::tool_schema_document() else {
+        panic!("object schema");
+    };
+    let Some(Document::Object(properties)) = schema.get("properties") else {
+        panic!("properties");
+    };
+    let mut names: Vec<_> = properties.keys().map(String::as_str).collect();
+    names.sort();
+    assert_eq!(names, ["apiKey", "customer", "page", "page_size"]);
+    assert_eq!(
+        schema.get("required"),
+        Some(&Document::Array(vec![
+            Document::String("customer".to_string()),
+            Document::String("page".to_string()),
+        ]))
+    );
+}
+
+#[test]
+fn advertised_input_deserializes() {
+    let parsed: ListOrders = serde_json::from_value(json!({
+        "customer": "c-1",
+        "page": 2,
+        "apiKey": "secret",
+    }))
+    .expect("parseable");
+    assert_eq!(
+        parsed,
+        ListOrders {
+            customer: "c-1".to_string(),
+            pagination: Pagination {
+                page: 2,
+                page_size: None,
+            },
+            auth: Some(AuthContext {
+                api_key: "secret".to_string(),
+            }),
+        }
+    );
+
+    let partial = ListOrders::parse_partial(r#"{"customer": "c-1", "page": 3, "apiK"#);
+    assert_eq!(partial.customer.as_deref(), Some("c-1"));
+    assert_eq!(
+        partial.pagination,
+        Some(Pagination {
+            page: 3,
+            page_size: None,
+        })
+    );
+    assert_eq!(partial.auth, None);
+}
+
+#[derive(Debug, PartialEq, AgentToolParameter, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum Filter {
+    ByPrice { max: i32 },
+    ByName { name: String },
+}
+
+#[derive(Debug, PartialEq, AgentTool, Deserialize)]
+struct FilterProducts {
+    query: String,
+    #[serde(flatten)]
+    filter: Filter,
+}
+
+#[derive(Debug, PartialEq, AgentTool, Deserialize)]
+struct BrowseProducts {
+    #[serde(flatten)]
+    pagination: Pagination,
+    #[serde(flatten)]
+    filter: Option<Filter>,
+    #[serde(flatten)]
+    auth: AuthContext,
+}
+
+#[test]
+fn flattened_enums_keep_their_branches() {
+    assert_eq!(
+        FilterProducts::tool_schema_json(),
+        json!({
+            "type": "object",
+            "properties": { "query": { "type": "string" } },
+            "required": ["query"],
+            "oneOf": [
+                {
+                    "type": "object",
+                    "properties": {
+                        "kind": { "type": "string", "enum": ["by_price"] },
+                        "max": { "type": "integer" },
+                    },
+                    "required": ["kind", "max"],
+                },
+                {
+                    "type": "object",
+                    "properties": {
+                        "kind": { "type": "string", "enum": ["by_name"] },
+                        "name": { "type": "string" },
+                    },
+                    "required": ["kind", "name"],
+                },
+            ],
+        })
+    );
+
+    let parsed: FilterProducts = serde_json::from_value(json!({
+        "query": "mug",
+        "kind": "by_price",
+        "max": 10,
+    }))
+    .expect("parseable");
+    assert_eq!(parsed.filter, Filter::ByPrice { max: 10 });
+}
+
+#[test]
+fn optional_flattened_enums_do_not_constrain_the_input() {
+    let schema = BrowseProducts::tool_schema_json();
+    assert_eq!(schema["required"], json!(["page", "apiKey"]));
+    assert_eq!(
+        schema["anyOf"][0],
+        json!({ "oneOf": Filter::parameter_schema_json()["oneOf"] })
+    );
+    assert_eq!(schema["anyOf"][1], json!({}));
+    assert!(schema.get("allOf").is_none());
+
+    let parsed: BrowseProducts = serde_json::from_value(json!({
+        "page": 1,
+        "apiKey": "secret",
+    }))
+    .expect("parseable");
+    assert_eq!(parsed.filter, None);
+}
@@ -0,0 +1,118 @@
+use derive_agent_tools::AgentTool;
+use derive_agent_tools::format::{ToolFormat, ToolInfo};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+
+#[derive(Debug, PartialEq, AgentTool, Deserialize)]
+#[tool(name = "weather", description = "Get the weather", read_only)]
+struct WeatherTool {
+    #[tool(required)]
+    city: String,
+}
+
+/// An in-house gateway envelope.
+struct Gateway;
+
+impl ToolFormat for Gateway {
+    type Definition = Value;
+    type Call = Value;
+    type Error = String;
+
+    fn definition(tool: ToolInfo) -> Value {
+        json!({
+            "tool": {
+                "id": tool.name,
+                "doc": tool.description,
+                "safe": tool.annotations.read_only == Some(true),
+                "input": tool.schema.to_json(),
+            }
+        })
+    }
+
+    fn parse_call<T: AgentTool + DeserializeOwned>(call: &Value) -> Result<T, String> {
+        if call["tool"] != T::tool_name() {
+            return Err(format!("not a call to {}", T::tool_name()));
+        }
+        serde_json::from_value(call["args"].clone()).map_err(|err| err.to_string())
+    }
+}
+
+#[test]
+fn renders_and_parses_custom_formats() {
+    assert_eq!(
+        WeatherTool::definition::<Gateway>(),
+        json!({
+            "tool": {
+                "id": "weather",
+                "doc": "Get the weather",
+                "safe": true,
+                "input": WeatherTool::tool_schema_json(),
+            }
+        })
+    );
+
+    let call = json!({ "tool": "weather", "args": { "city": "Lima" } });
+    assert_eq!(
+        WeatherTool::parse_call::<Gateway>(&call),
+        Ok(WeatherTool {
+            city: "Lima".to_string()
+        })
+    );
+    let call = json!({ "tool": "stocks", "args": {} });
+    assert_eq!(
+        WeatherTool::parse_call::<Gateway>(&call),
+        Err("not a call to weather".to_string())
+    );
+}
+
+#[test]
+fn json_schema_format() {
+    use derive_agent_tools::format::JsonSchema;
+
+    assert_eq!(
+        WeatherTool::definition::<JsonSchema>(),
+        json!({
+            "name": "weather",
+            "description": "Get the weather",
+            "parameters": WeatherTool::tool_schema_json(),
+        })
+    );
+    let parsed = WeatherTool::parse_call::<JsonSchema>(&json!({ "city": "Oslo" })).unwrap();
+    assert_eq!(parsed.city, "Oslo");
+    assert!(WeatherTool::parse_call::<JsonSchema>(&json!({})).is_err());
+}
+
+#[test]
+#[cfg(feature = "bedrock")]
+fn bedrock_format() {
+    use std::collections::HashMap;
+
+    use aws_sdk_bedrockruntime::types::ToolUseBlock;
+    use aws_smithy_types::Document;
+    use derive_agent_tools::bedrock::ToolUseError;
+    use derive_agent_tools::format::Bedrock;
+
+    assert_eq!(
+        WeatherTool::definition::<Bedrock>(),
+        WeatherTool::tool_spec()
+    );
+
+    let block = |name: &str| {
+        ToolUseBlock::builder()
+            .tool_use_id("t-1")
+            .name(name)
+            .input(Document::Object(HashMap::from([(
+                "city".to_string(),
+                Document::String("Quito".to_string()),
+            )])))
+            .build()
+            .unwrap()
+    };
+    let parsed = WeatherTool::parse_call::<Bedrock>(&block("weather")).unwrap();
+    assert_eq!(parsed.city, "Quito");
+    assert!(matches!(
+        WeatherTool::parse_call::<Bedrock>(&block("stocks")),
+        Err(ToolUseError::NameMismatch { .. })
+    ));
+}
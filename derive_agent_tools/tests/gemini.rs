@@ -0,0 +1,159 @@
+#![cfg(feature = "gemini")]
+
+use std::collections::HashMap;
+
+use derive_agent_tools::gemini::gemini_schema;
+use derive_agent_tools::{AgentTool, AgentToolParameter};
+use serde::Deserialize;
+use serde_json::json;
+
+#[allow(dead_code)]
+#[derive(AgentToolParameter, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Unit {
+    Celsius,
+    Fahrenheit,
+}
+
+#[allow(dead_code)]
+#[derive(AgentTool, Deserialize)]
+#[tool(name = "get_weather", description = "Get the weather")]
+struct WeatherTool {
+    #[tool(required, description = "City name")]
+    city: String,
+    unit: Option<Unit>,
+    days: Vec<u8>,
+    labels: HashMap<String, String>,
+}
+
+#[allow(dead_code)]
+#[derive(AgentToolParameter, Deserialize)]
+#[serde(tag = "by", rename_all = "snake_case")]
+enum Place {
+    City { city: String },
+    Coordinates { lat: f64, lon: f64 },
+}
+
+#[allow(dead_code)]
+#[derive(AgentToolParameter, Deserialize)]
+#[serde(tag = "units", rename_all = "snake_case")]
+enum Units {
+    Metric,
+    Imperial,
+}
+
+#[allow(dead_code)]
+#[derive(AgentTool, Deserialize)]
+struct ForecastTool {
+    #[serde(flatten)]
+    place: Place,
+    #[serde(flatten)]
+    units: Units,
+    /// First and last day
+    window: (u8, u8),
+    slot: (String, u32),
+    extra: serde_json::Value,
+}
+
+#[test]
+fn function_declaration_shape() {
+    assert_eq!(
+        WeatherTool::gemini_function_declaration(),
+        json!({
+            "name": "get_weather",
+            "description": "Get the weather",
+            "parameters": {
+                "type": "OBJECT",
+                "properties": {
+                    "city": { "type": "STRING", "description": "City name" },
+                    "unit": { "type": "STRING", "enum": ["celsius", "fahrenheit"] },
+                    "days": { "type": "ARRAY", "items": { "type": "INTEGER" } },
+                    "labels": {
+                        "type": "STRING",
+                        "description": "A JSON object, encoded as a string.",
+                    },
+                },
+                "required": ["city", "days", "labels"],
+            },
+        })
+    );
+}
+
+#[test]
+fn nullable_and_unions_are_downgraded() {
+    assert_eq!(
+        gemini_schema(&json!({ "type": ["string", "null"], "format": "uri" })),
+        json!({ "type": "STRING", "nullable": true })
+    );
+    assert_eq!(
+        gemini_schema(&json!({
+            "anyOf": [{ "type": "integer", "format": "int64" }, { "type": "null" }],
+        })),
+        json!({ "type": "INTEGER", "format": "int64", "nullable": true })
+    );
+    assert_eq!(
+        gemini_schema(&json!({
+            "oneOf": [
+                { "type": "object", "properties": { "kind": { "const": "a" } } },
+                { "type": "string", "enum": ["b", null] },
+            ],
+            "additionalProperties": false,
+        })),
+        json!({
+            "anyOf": [
+                {
+                    "type": "OBJECT",
+                    "properties": { "kind": { "type": "STRING", "enum": ["a"] } },
+                },
+                { "type": "STRING", "enum": ["b"] },
+            ],
+            "nullable": true,
+        })
+    );
+}
+
+#[test]
+fn unsupported_constructs_are_downgraded() {
+    assert_eq!(
+        ForecastTool::gemini_function_declaration()["parameters"],
+        json!({
+            "type": "OBJECT",
+            "properties": {
+                "by": { "type": "STRING", "enum": ["city", "coordinates"] },
+                "city": { "type": "STRING" },
+                "lat": { "type": "NUMBER" },
+                "lon": { "type": "NUMBER" },
+                "units": { "type": "STRING", "enum": ["metric", "imperial"] },
+                "window": {
+                    "type": "ARRAY",
+                    "description": "First and last day",
+                    "items": { "type": "INTEGER" },
+                    "minItems": 2,
+                    "maxItems": 2,
+                },
+                "slot": {
+                    "type": "ARRAY",
+                    "items": { "anyOf": [{ "type": "STRING" }, { "type": "INTEGER" }] },
+                    "minItems": 2,
+                    "maxItems": 2,
+                },
+                "extra": {
+                    "type": "STRING",
+                    "description": "Any JSON value, encoded as a string.",
+                },
+            },
+            "required": ["window", "slot", "extra"],
+        })
+    );
+    assert_eq!(
+        gemini_schema(&json!({
+            "type": "object",
+            "description": "Tags",
+            "additionalProperties": { "type": "string" },
+        })),
+        json!({
+            "type": "STRING",
+            "description": "Tags\n\nA JSON object, encoded as a string.",
+        })
+    );
+}
@@ -0,0 +1,128 @@
+use std::future::Future;
+use std::pin::pin;
+use std::task::{Context, Poll, Waker};
+
+use derive_agent_tools::{AgentTool, AgentToolHandler, SyncAgentToolHandler};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+/// Polls `future` to completion; none of the futures here ever wait.
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut cx = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
+
+#[derive(AgentTool, Deserialize)]
+struct DivideTool {
+    #[tool(required)]
+    a: f64,
+    #[tool(required)]
+    b: f64,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+struct Quotient {
+    value: f64,
+}
+
+impl SyncAgentToolHandler for DivideTool {
+    type Output = Quotient;
+    type Error = &'static str;
+
+    fn call_sync(self, _ctx: &()) -> Result<Quotient, &'static str> {
+        if self.b == 0.0 {
+            return Err("division by zero");
+        }
+        Ok(Quotient {
+            value: self.a / self.b,
+        })
+    }
+}
+
+struct Greeter {
+    greeting: &'static str,
+}
+
+#[derive(AgentTool, Deserialize)]
+struct GreetTool {
+    #[tool(required)]
+    name: String,
+}
+
+impl AgentToolHandler<Greeter> for GreetTool {
+    type Output = String;
+    type Error = String;
+
+    async fn call(self, ctx: &Greeter) -> Result<String, String> {
+        Ok(format!("{}, {}!", ctx.greeting, self.name))
+    }
+}
+
+#[derive(AgentTool, Deserialize)]
+#[tool(handler = add)]
+struct AddTool {
+    #[tool(required)]
+    a: i64,
+    #[tool(required)]
+    b: i64,
+}
+
+fn add(args: AddTool) -> Result<i64, String> {
+    args.a.checked_add(args.b).ok_or_else(|| "overflow".into())
+}
+
+#[derive(AgentTool, Deserialize)]
+#[tool(async_handler = echo::run, context = Greeter)]
+struct EchoTool {
+    #[tool(required)]
+    text: String,
+}
+
+mod echo {
+    use super::{EchoTool, Greeter};
+
+    pub async fn run(args: EchoTool, ctx: &Greeter) -> Result<Vec<String>, std::fmt::Error> {
+        Ok(vec![ctx.greeting.to_string(), args.text])
+    }
+}
+
+#[test]
+fn sync_handlers_are_async_handlers() {
+    let tool = DivideTool { a: 1.0, b: 4.0 };
+    assert_eq!(tool.call_sync(&()), Ok(Quotient { value: 0.25 }));
+
+    let tool = DivideTool { a: 1.0, b: 0.0 };
+    assert_eq!(block_on(tool.call(&())), Err("division by zero"));
+}
+
+#[test]
+fn handlers_receive_context() {
+    let ctx = Greeter { greeting: "Hello" };
+    let tool: GreetTool = serde_json::from_value(json!({ "name": "Ada" })).unwrap();
+    assert_eq!(block_on(tool.call(&ctx)).unwrap(), "Hello, Ada!");
+}
+
+#[test]
+fn handler_attribute_erases_output() {
+    let output = block_on(AddTool { a: 2, b: 40 }.call(&())).unwrap();
+    assert_eq!(output, json!(42));
+
+    // Without `context` the generated impl accepts any context type.
+    let ctx = Greeter { greeting: "Hi" };
+    let error = AddTool { a: i64::MAX, b: 1 }.call_sync(&ctx).unwrap_err();
+    assert_eq!(error, "overflow");
+}
+
+#[test]
+fn async_handler_attribute_with_context() {
+    let ctx = Greeter { greeting: "Hi" };
+    let tool = EchoTool {
+        text: "there".to_string(),
+    };
+    assert_eq!(block_on(tool.call(&ctx)).unwrap(), json!(["Hi", "there"]));
+}
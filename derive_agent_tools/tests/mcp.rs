@@ -0,0 +1,178 @@
+#![cfg(feature = "mcp")]
+
+use std::io::{BufRead, BufReader, Write};
+use std::sync::Arc;
+
+use derive_agent_tools::AgentTool;
+use derive_agent_tools::mcp::McpServer;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+#[derive(AgentTool, Deserialize)]
+#[tool(
+    name = "add",
+    title = "Adder",
+    description = "Add two numbers",
+    read_only,
+    open_world = false
+)]
+struct AddTool {
+    #[tool(required)]
+    a: i64,
+    #[tool(required)]
+    b: i64,
+}
+
+#[derive(AgentTool, Deserialize)]
+#[tool(name = "divide", destructive = false, idempotent)]
+struct DivideTool {
+    #[tool(required)]
+    a: f64,
+    #[tool(required)]
+    b: f64,
+}
+
+#[derive(Serialize)]
+struct Quotient {
+    value: f64,
+}
+
+fn server() -> McpServer {
+    let mut server = McpServer::new("calculator", "1.2.3");
+    server
+        .register(|args: AddTool| Ok::<_, String>(args.a + args.b))
+        .register(|args: DivideTool| {
+            if args.b == 0.0 {
+                Err("division by zero")
+            } else {
+                Ok(Quotient {
+                    value: args.a / args.b,
+                })
+            }
+        });
+    server
+}
+
+#[test]
+fn tool_descriptors() {
+    assert_eq!(
+        AddTool::mcp_tool(),
+        json!({
+            "name": "add",
+            "title": "Adder",
+            "description": "Add two numbers",
+            "inputSchema": AddTool::tool_schema_json(),
+            "annotations": {
+                "title": "Adder",
+                "readOnlyHint": true,
+                "openWorldHint": false,
+            },
+        })
+    );
+    assert_eq!(
+        DivideTool::mcp_tool()["annotations"],
+        json!({ "destructiveHint": false, "idempotentHint": true })
+    );
+}
+
+/// Runs the server on a thread connected through in-process pipes and sends
+/// it one message per line, returning the parsed responses.
+fn round_trip(messages: &[Value]) -> Vec<Value> {
+    let (server_in, mut client_out) = std::io::pipe().expect("pipe");
+    let (client_in, server_out) = std::io::pipe().expect("pipe");
+    let server = Arc::new(server());
+    let handle = std::thread::spawn(move || server.serve(BufReader::new(server_in), server_out));
+
+    for message in messages {
+        writeln!(client_out, "{message}").expect("write request");
+    }
+    writeln!(client_out, "not json").expect("write request");
+    drop(client_out);
+
+    let responses = BufReader::new(client_in)
+        .lines()
+        .map(|line| serde_json::from_str(&line.expect("read response")).expect("json response"))
+        .collect();
+    handle.join().expect("server thread").expect("serve");
+    responses
+}
+
+#[test]
+fn serves_json_rpc_over_pipes() {
+    let responses = round_trip(&[
+        json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": { "protocolVersion": "2025-03-26", "capabilities": {} },
+        }),
+        json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }),
+        json!({ "jsonrpc": "2.0", "id": 2, "method": "tools/list" }),
+        json!({
+            "jsonrpc": "2.0",
+            "id": 3,
+            "method": "tools/call",
+            "params": { "name": "add", "arguments": { "a": 2, "b": 40 } },
+        }),
+        json!({
+            "jsonrpc": "2.0",
+            "id": 4,
+            "method": "tools/call",
+            "params": { "name": "divide", "arguments": { "a": 1.0, "b": 4.0 } },
+        }),
+        json!({
+            "jsonrpc": "2.0",
+            "id": 5,
+            "method": "tools/call",
+            "params": { "name": "divide", "arguments": { "a": 1.0, "b": 0.0 } },
+        }),
+        json!({
+            "jsonrpc": "2.0",
+            "id": 6,
+            "method": "tools/call",
+            "params": { "name": "add", "arguments": { "a": "two" } },
+        }),
+        json!({
+            "jsonrpc": "2.0",
+            "id": 7,
+            "method": "tools/call",
+            "params": { "name": "multiply", "arguments": {} },
+        }),
+        json!({ "jsonrpc": "2.0", "id": 8, "method": "resources/list" }),
+    ]);
+
+    // The notification gets no response; the trailing garbage line does.
+    assert_eq!(responses.len(), 9);
+
+    assert_eq!(
+        responses[0]["result"],
+        json!({
+            "protocolVersion": "2025-03-26",
+            "capabilities": { "tools": { "listChanged": false } },
+            "serverInfo": { "name": "calculator", "version": "1.2.3" },
+        })
+    );
+
+    let tools = responses[1]["result"]["tools"].as_array().expect("tools");
+    assert_eq!(tools, &vec![AddTool::mcp_tool(), DivideTool::mcp_tool()]);
+
+    assert_eq!(
+        responses[2]["result"],
+        json!({ "content": [{ "type": "text", "text": "42" }], "isError": false })
+    );
+    assert_eq!(
+        responses[3]["result"]["structuredContent"],
+        json!({ "value": 0.25 })
+    );
+    assert_eq!(responses[4]["id"], 5);
+    assert_eq!(responses[4]["result"]["isError"], true);
+    assert_eq!(
+        responses[4]["result"]["content"][0]["text"],
+        "division by zero"
+    );
+    assert_eq!(responses[5]["result"]["isError"], true);
+    assert_eq!(responses[6]["error"]["code"], -32602);
+    assert_eq!(responses[7]["error"]["code"], -32601);
+    assert_eq!(responses[8]["id"], Value::Null);
+    assert_eq!(responses[8]["error"]["code"], -32700);
+}
@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+
+use derive_agent_tools::{AgentTool, AgentToolParameter};
+use serde::Deserialize;
+use serde_json::json;
+
+#[allow(dead_code)]
+#[derive(AgentToolParameter, Deserialize)]
+#[tool(description = "A postal address")]
+struct Address {
+    #[tool(required, description = "Street and number")]
+    street: String,
+    #[tool(required)]
+    city: String,
+    zip: Option<String>,
+}
+
+#[allow(dead_code)]
+#[derive(AgentToolParameter, Deserialize)]
+struct Filter {
+    #[tool(required)]
+    field: String,
+    values: Vec<String>,
+}
+
+#[allow(dead_code)]
+#[derive(AgentToolParameter, Deserialize)]
+struct Tag(String);
+
+#[allow(dead_code)]
+#[derive(AgentTool, Deserialize)]
+#[tool(description = "Ship a parcel")]
+struct ShipTool {
+    #[tool(required, description = "Where to ship to")]
+    destination: Address,
+    origin: Option<Address>,
+    filters: Vec<Filter>,
+    tags: Vec<Tag>,
+    metadata: HashMap<String, i64>,
+}
+
+/// A tool that is also used as another tool's parameter.
+#[allow(dead_code)]
+#[derive(AgentTool, AgentToolParameter, Deserialize)]
+#[tool(name = "lookup", read_only, description = "Look something up")]
+struct LookupTool {
+    #[tool(required)]
+    query: String,
+}
+
+#[test]
+fn parameter_schema_walks_fields() {
+    assert_eq!(
+        Address::parameter_schema_json(),
+        json!({
+            "type": "object",
+            "description": "A postal address",
+            "properties": {
+                "street": { "type": "string", "description": "Street and number" },
+                "city": { "type": "string" },
+                "zip": { "type": "string" },
+            },
+            "required": ["street", "city"],
+        })
+    );
+    assert_eq!(Tag::parameter_schema_json(), json!({ "type": "string" }));
+    assert_eq!(LookupTool::tool_name(), "lookup");
+    assert_eq!(
+        LookupTool::parameter_schema_json(),
+        json!({
+            "type": "object",
+            "description": "Look something up",
+            "properties": { "query": { "type": "string" } },
+            "required": ["query"],
+        })
+    );
+}
+
+#[test]
+fn tool_schema_embeds_nested_schemas() {
+    let schema = ShipTool::tool_schema_json();
+    let props = &schema["properties"];
+
+    assert_eq!(props["destination"]["type"], "object");
+    assert_eq!(props["destination"]["description"], "Where to ship to");
+    assert_eq!(props["destination"]["required"], json!(["street", "city"]));
+    assert_eq!(props["origin"]["description"], "A postal address");
+    assert_eq!(
+        props["filters"]["items"]["properties"]["values"]["items"],
+        json!({ "type": "string" })
+    );
+    assert_eq!(props["tags"]["items"], json!({ "type": "string" }));
+    assert_eq!(
+        props["metadata"],
+        json!({ "type": "object", "additionalProperties": { "type": "integer" } })
+    );
+    assert_eq!(
+        schema["required"],
+        json!(["destination", "filters", "tags", "metadata"])
+    );
+}
+
+#[test]
+#[cfg(feature = "bedrock")]
+fn bedrock_spec_embeds_nested_schemas() {
+    use aws_sdk_bedrockruntime::types::ToolInputSchema;
+    use aws_smithy_types::Document;
+
+    let spec = ShipTool::tool_spec();
+    let Some(ToolInputSchema::Json(Document::Object(schema))) = spec.input_schema() else {
+        panic!("expected a JSON input schema");
+    };
+    let Some(Document::Object(props)) = schema.get("properties") else {
+        panic!("expected properties");
+    };
+    let Some(Document::Object(destination)) = props.get("destination") else {
+        panic!("expected an object schema for destination");
+    };
+    assert_eq!(
+        destination.get("type"),
+        Some(&Document::String("object".to_string()))
+    );
+    let Some(Document::Object(address_props)) = destination.get("properties") else {
+        panic!("expected nested properties");
+    };
+    assert!(address_props.contains_key("street"));
+    assert!(address_props.contains_key("zip"));
+}
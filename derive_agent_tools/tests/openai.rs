@@ -0,0 +1,141 @@
+#![cfg(feature = "openai")]
+
+use derive_agent_tools::{AgentTool, AgentToolParameter, openai};
+use serde::Deserialize;
+use serde_json::json;
+
+#[allow(dead_code)]
+#[derive(AgentToolParameter, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Unit {
+    Celsius,
+    Fahrenheit,
+}
+
+#[allow(dead_code)]
+#[derive(AgentToolParameter, Deserialize)]
+struct Location {
+    #[tool(required)]
+    city: String,
+    country: Option<String>,
+}
+
+#[allow(dead_code)]
+#[derive(AgentTool, Deserialize)]
+#[tool(name = "get_weather", description = "Get the weather")]
+struct WeatherTool {
+    #[tool(required)]
+    location: Location,
+    unit: Option<Unit>,
+}
+
+#[test]
+fn chat_completions_shape() {
+    assert_eq!(
+        WeatherTool::openai_tool(false),
+        json!({
+            "type": "function",
+            "function": {
+                "name": "get_weather",
+                "description": "Get the weather",
+                "parameters": WeatherTool::tool_schema_json(),
+            },
+        })
+    );
+}
+
+#[test]
+fn responses_shape() {
+    assert_eq!(
+        WeatherTool::openai_response_tool(false),
+        json!({
+            "type": "function",
+            "name": "get_weather",
+            "description": "Get the weather",
+            "parameters": WeatherTool::tool_schema_json(),
+        })
+    );
+}
+
+#[test]
+fn strict_mode() {
+    let tool = WeatherTool::openai_response_tool(true);
+    assert_eq!(tool["strict"], true);
+    assert_eq!(
+        tool["parameters"],
+        json!({
+            "type": "object",
+            "additionalProperties": false,
+            "properties": {
+                "location": {
+                    "type": "object",
+                    "additionalProperties": false,
+                    "properties": {
+                        "city": { "type": "string" },
+                        "country": { "type": ["string", "null"] },
+                    },
+                    "required": ["city", "country"],
+                },
+                "unit": {
+                    "type": ["string", "null"],
+                    "enum": ["celsius", "fahrenheit", null],
+                },
+            },
+            "required": ["location", "unit"],
+        })
+    );
+    assert_eq!(
+        WeatherTool::openai_tool(true)["function"]["parameters"],
+        tool["parameters"]
+    );
+}
+
+#[test]
+fn strict_nullable_unions() {
+    let schema = openai::strict_schema(json!({
+        "type": "object",
+        "properties": {
+            "choice": { "oneOf": [{ "type": "integer" }, { "type": "string" }] },
+        },
+    }));
+    assert_eq!(
+        schema["properties"]["choice"],
+        json!({
+            "anyOf": [
+                { "anyOf": [{ "type": "integer" }, { "type": "string" }] },
+                { "type": "null" },
+            ],
+        })
+    );
+}
+
+#[test]
+fn strict_recurses_into_tuples_and_all_of() {
+    let point = json!({
+        "type": "object",
+        "properties": { "x": { "type": "number" }, "label": { "type": "string" } },
+        "required": ["x"],
+    });
+    let strict_point = json!({
+        "type": "object",
+        "properties": {
+            "x": { "type": "number" },
+            "label": { "type": ["string", "null"] },
+        },
+        "required": ["label", "x"],
+        "additionalProperties": false,
+    });
+    let schema = openai::strict_schema(json!({
+        "type": "object",
+        "properties": {
+            "pair": { "type": "array", "prefixItems": [point.clone(), { "type": "string" }] },
+        },
+        "required": ["pair"],
+        "allOf": [{ "oneOf": [point] }],
+    }));
+    assert_eq!(
+        schema["properties"]["pair"]["prefixItems"],
+        json!([strict_point.clone(), { "type": "string" }])
+    );
+    assert_eq!(schema["allOf"], json!([{ "anyOf": [strict_point] }]));
+}
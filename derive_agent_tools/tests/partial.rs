@@ -0,0 +1,159 @@
+use derive_agent_tools::partial::parse_json;
+use derive_agent_tools::{AgentTool, AgentToolParameter, PartialAgentTool};
+use serde::Deserialize;
+use serde_json::json;
+
+#[derive(Debug, PartialEq, AgentToolParameter, Deserialize)]
+struct Author {
+    #[tool(required)]
+    name: String,
+}
+
+#[allow(dead_code)]
+#[derive(AgentTool, Deserialize)]
+struct WriteFile {
+    #[tool(required)]
+    path: String,
+    #[tool(required)]
+    content: String,
+    overwrite: Option<bool>,
+    tags: Vec<String>,
+    author: Option<Author>,
+}
+
+#[test]
+fn closes_open_containers_and_strings() {
+    assert_eq!(parse_json(""), None);
+    assert_eq!(parse_json("  {"), Some(json!({})));
+    assert_eq!(parse_json(r#"{"a"#), Some(json!({})));
+    assert_eq!(parse_json(r#"{"a": "#), Some(json!({})));
+    assert_eq!(parse_json(r#"{"a": "he"#), Some(json!({ "a": "he" })));
+    assert_eq!(
+        parse_json(r#"{"a": [1, {"b": nu"#),
+        Some(json!({ "a": [1, {}] }))
+    );
+    assert_eq!(parse_json(r#"[true, fal"#), Some(json!([true])));
+    assert_eq!(parse_json(r#"{"a": 1.5}"#), Some(json!({ "a": 1.5 })));
+    assert_eq!(parse_json(r#"{"a": 12"#), Some(json!({})));
+    assert_eq!(parse_json(r#"{"a": 12,"#), Some(json!({ "a": 12 })));
+}
+
+#[test]
+fn drops_cut_off_escapes() {
+    assert_eq!(parse_json(r#""line\"#), Some(json!("line")));
+    assert_eq!(parse_json(r#""line\n"#), Some(json!("line\n")));
+    assert_eq!(parse_json(r#""back\\"#), Some(json!("back\\")));
+    assert_eq!(parse_json(r#""caf\u00"#), Some(json!("caf")));
+    assert_eq!(parse_json(r#""café"#), Some(json!("café")));
+    assert_eq!(parse_json(r#""smile \ud83d"#), Some(json!("smile ")));
+    assert_eq!(parse_json(r#""smile 😀"#), Some(json!("smile 😀")));
+}
+
+#[test]
+fn rejects_invalid_prefixes() {
+    assert_eq!(parse_json("x"), None);
+    assert_eq!(parse_json(r#"{"a" 1"#), None);
+    assert_eq!(parse_json(r#"{"a": 1 "b""#), None);
+    assert_eq!(parse_json("[1 2"), None);
+    assert_eq!(parse_json("{1: 2}"), None);
+}
+
+#[test]
+fn partial_view_of_derived_tool() {
+    let partial = WriteFile::parse_partial(
+        r#"{"path": "notes.md", "overwrite": true, "tags": ["a", "b"], "author": {"name": "Ad"#,
+    );
+    assert_eq!(partial.path.as_deref(), Some("notes.md"));
+    assert_eq!(partial.content, None);
+    assert_eq!(partial.overwrite, Some(true));
+    assert_eq!(partial.tags, Some(vec!["a".to_string(), "b".to_string()]));
+    assert_eq!(
+        partial.author,
+        Some(Author {
+            name: "Ad".to_string()
+        })
+    );
+
+    let partial = WriteFile::parse_partial(r#"{"path": 3, "content": "Dear"#);
+    assert_eq!(partial.path, None);
+    assert_eq!(partial.content.as_deref(), Some("Dear"));
+
+    let partial = WriteFile::parse_partial("not json");
+    assert_eq!(partial.path, None);
+    assert_eq!(partial.tags, None);
+}
+
+/// Parsed from a `"lat,lon"` string; deliberately not `Deserialize`.
+#[derive(Debug, PartialEq, AgentToolParameter)]
+struct Coordinates {
+    #[tool(required)]
+    lat: f64,
+    #[tool(required)]
+    lon: f64,
+}
+
+fn coordinates<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Coordinates, D::Error> {
+    let text = String::deserialize(deserializer)?;
+    let (lat, lon) = text
+        .split_once(',')
+        .ok_or_else(|| serde::de::Error::custom("expected `lat,lon`"))?;
+    let parse = |part: &str| part.trim().parse().map_err(serde::de::Error::custom);
+    Ok(Coordinates {
+        lat: parse(lat)?,
+        lon: parse(lon)?,
+    })
+}
+
+#[allow(dead_code)]
+#[derive(AgentTool, Deserialize)]
+struct Locate {
+    #[tool(required)]
+    label: String,
+    #[serde(deserialize_with = "coordinates")]
+    at: Coordinates,
+}
+
+#[test]
+fn partial_view_leaves_out_custom_deserializers() {
+    let partial = Locate::parse_partial(r#"{"label": "home", "at": "59.9,10"#);
+    assert_eq!(partial.label.as_deref(), Some("home"));
+
+    let tool: Locate = serde_json::from_str(r#"{"label": "home", "at": "59.9, 10.7"}"#).unwrap();
+    assert_eq!(
+        tool.at,
+        Coordinates {
+            lat: 59.9,
+            lon: 10.7
+        }
+    );
+}
+
+mod search {
+    use derive_agent_tools::AgentTool;
+    use derive_agent_tools::schema::{Schema, SchemaType};
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    struct Cursor(#[allow(dead_code)] u64);
+
+    /// Public tool with a private field of a private type, which stays
+    /// private on the partial view.
+    #[allow(dead_code)]
+    #[derive(AgentTool, Deserialize)]
+    pub struct SearchTool {
+        #[tool(required)]
+        pub query: String,
+        #[tool(schema = cursor_schema)]
+        cursor: Option<Cursor>,
+    }
+
+    fn cursor_schema() -> Schema {
+        Schema::typed(SchemaType::Integer)
+    }
+}
+
+#[test]
+fn partial_view_keeps_field_visibility() {
+    let partial = search::SearchTool::parse_partial(r#"{"query": "ru"#);
+    assert_eq!(partial.query.as_deref(), Some("ru"));
+}
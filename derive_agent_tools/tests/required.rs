@@ -0,0 +1,84 @@
+use derive_agent_tools::{AgentTool, AgentToolParameter};
+use serde::{Deserialize, Deserializer};
+use serde_json::json;
+
+fn nullable<'de, D: Deserializer<'de>>(de: D) -> Result<Option<String>, D::Error> {
+    Option::deserialize(de)
+}
+
+#[derive(Debug, AgentToolParameter, Deserialize)]
+#[allow(dead_code)]
+struct Coordinates {
+    latitude: f64,
+    longitude: f64,
+}
+
+#[derive(Debug, AgentTool, Deserialize)]
+#[allow(dead_code)]
+struct PlaceOrder {
+    item: String,
+    quantity: u32,
+    note: Option<String>,
+    #[serde(default)]
+    gift: bool,
+    #[tool(optional)]
+    priority: u8,
+    #[tool(required)]
+    #[serde(deserialize_with = "nullable")]
+    coupon: Option<String>,
+    delivery: Option<Coordinates>,
+}
+
+#[test]
+fn non_option_fields_are_required() {
+    assert_eq!(
+        PlaceOrder::tool_schema_json()["required"],
+        json!(["item", "quantity", "coupon"])
+    );
+    assert_eq!(
+        Coordinates::parameter_schema_json()["required"],
+        json!(["latitude", "longitude"])
+    );
+}
+
+#[test]
+fn inferred_required_fields_match_serde() {
+    let missing_quantity = serde_json::from_value::<PlaceOrder>(json!({
+        "item": "mug",
+        "priority": 1,
+        "coupon": null,
+    }));
+    assert!(missing_quantity.is_err());
+
+    let missing_coupon = serde_json::from_value::<PlaceOrder>(json!({
+        "item": "mug",
+        "quantity": 2,
+        "priority": 1,
+    }));
+    assert!(missing_coupon.is_err());
+
+    let order: PlaceOrder = serde_json::from_value(json!({
+        "item": "mug",
+        "quantity": 2,
+        "priority": 1,
+        "coupon": null,
+    }))
+    .expect("parseable");
+    assert_eq!(order.quantity, 2);
+    assert!(!order.gift);
+}
+
+#[derive(Debug, AgentTool, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+#[allow(dead_code)]
+enum Counter {
+    Add { amount: i64, label: Option<String> },
+    Reset,
+}
+
+#[test]
+fn variant_fields_are_inferred_too() {
+    let schema = Counter::tool_schema_json();
+    assert_eq!(schema["oneOf"][0]["required"], json!(["op", "amount"]));
+    assert_eq!(schema["oneOf"][1]["required"], json!(["op"]));
+}
@@ -0,0 +1,170 @@
+use derive_agent_tools::schema::{Constraints, Property, Schema, SchemaType, SchemaValue};
+use derive_agent_tools::{AgentTool, AgentToolParameter};
+use serde::Deserialize;
+
+/// A manual implementation only describes the schema once.
+#[derive(Deserialize)]
+struct Email(#[allow(dead_code)] String);
+
+impl AgentToolParameter for Email {
+    fn parameter_schema() -> Schema {
+        Schema {
+            constraints: Constraints {
+                format: Some("email".to_string()),
+                max_length: Some(254),
+                ..Constraints::default()
+            },
+            ..Schema::typed(SchemaType::String)
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(AgentTool, Deserialize)]
+#[tool(description = "Send a message")]
+struct SendTool {
+    #[tool(required, description = "Recipient")]
+    to: Email,
+    retries: Option<u8>,
+}
+
+#[test]
+fn derived_schema_is_a_model() {
+    let schema = SendTool::tool_schema();
+    assert_eq!(schema.ty, Some(SchemaType::Object));
+    let properties = schema.properties.as_deref().unwrap();
+    assert_eq!(
+        properties[0],
+        Property {
+            name: "to".to_string(),
+            required: true,
+            schema: Email::parameter_schema().with_description("Recipient"),
+        }
+    );
+    assert_eq!(properties[1].schema, Schema::typed(SchemaType::Integer));
+}
+
+#[test]
+#[cfg(feature = "serde-json")]
+fn renders_json() {
+    use serde_json::json;
+
+    let schema = Schema {
+        constraints: Constraints {
+            minimum: Some(1.0),
+            maximum: Some(2.5),
+            min_items: Some(1),
+            ..Constraints::default()
+        },
+        ..Schema::array(Schema::reference("#/$defs/Item"))
+    };
+    assert_eq!(
+        schema.to_json(),
+        json!({
+            "type": "array",
+            "items": { "$ref": "#/$defs/Item" },
+            "minimum": 1,
+            "maximum": 2.5,
+            "minItems": 1,
+        })
+    );
+    assert_eq!(
+        SendTool::tool_schema_json(),
+        json!({
+            "type": "object",
+            "properties": {
+                "to": {
+                    "type": "string",
+                    "description": "Recipient",
+                    "format": "email",
+                    "maxLength": 254,
+                },
+                "retries": { "type": "integer" },
+            },
+            "required": ["to"],
+        })
+    );
+}
+
+#[test]
+#[cfg(feature = "bedrock")]
+fn renders_documents() {
+    use std::collections::HashMap;
+
+    use aws_smithy_types::{Document, Number};
+
+    let schema = Schema::map(Schema::typed(SchemaType::Number)).with_description("Scores");
+    let string = |s: &str| Document::String(s.to_string());
+    assert_eq!(
+        schema.to_document(),
+        Document::Object(HashMap::from([
+            ("type".to_string(), string("object")),
+            ("description".to_string(), string("Scores")),
+            (
+                "additionalProperties".to_string(),
+                Document::Object(HashMap::from([("type".to_string(), string("number"))])),
+            ),
+        ]))
+    );
+
+    let bounded = Schema {
+        constraints: Constraints {
+            minimum: Some(-3.0),
+            ..Constraints::default()
+        },
+        ..Schema::typed(SchemaType::Integer)
+    };
+    assert_eq!(
+        bounded.to_document(),
+        Document::Object(HashMap::from([
+            ("type".to_string(), string("integer")),
+            ("minimum".to_string(), Document::Number(Number::NegInt(-3))),
+        ]))
+    );
+}
+
+/// A renderer for a format the crate knows nothing about.
+#[derive(Debug, PartialEq)]
+struct Compact(String);
+
+impl SchemaValue for Compact {
+    fn string(value: &str) -> Self {
+        Compact(value.to_string())
+    }
+
+    fn number(value: f64) -> Self {
+        Compact(value.to_string())
+    }
+
+    fn unsigned(value: u64) -> Self {
+        Compact(value.to_string())
+    }
+
+    fn array(items: Vec<Self>) -> Self {
+        let items: Vec<_> = items.into_iter().map(|c| c.0).collect();
+        Compact(format!("[{}]", items.join(",")))
+    }
+
+    fn object(entries: Vec<(String, Self)>) -> Self {
+        let entries: Vec<_> = entries
+            .into_iter()
+            .map(|(k, v)| format!("{k}={}", v.0))
+            .collect();
+        Compact(format!("({})", entries.join(",")))
+    }
+}
+
+#[test]
+fn renders_custom_formats() {
+    let schema = Schema::object(vec![Property {
+        name: "unit".to_string(),
+        required: true,
+        schema: Schema::string_enum(["c", "f"]),
+    }]);
+    assert_eq!(
+        schema.render::<Compact>(),
+        Compact(
+            "(type=object,properties=(unit=(type=string,enum=[c,f])),required=[unit])".to_string()
+        )
+    );
+}
@@ -0,0 +1,135 @@
+use derive_agent_tools::{AgentTool, AgentToolParameter, PartialAgentTool};
+use serde::Deserialize;
+use serde_json::json;
+
+#[derive(Debug, PartialEq, AgentTool, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BookFlight {
+    #[tool(required)]
+    departure_airport: String,
+    #[tool(required)]
+    #[serde(rename = "to")]
+    arrival_airport: String,
+    #[serde(rename(serialize = "ignored", deserialize = "seatClass"))]
+    seat: Option<String>,
+    #[serde(alias = "pax", alias = "travellers")]
+    passenger_count: Option<u8>,
+    #[serde(default)]
+    flexible_dates: bool,
+    #[serde(skip)]
+    booking_ref: Option<String>,
+    #[serde(skip_deserializing)]
+    price: Option<f64>,
+    r#type: Option<String>,
+}
+
+#[allow(dead_code)]
+#[derive(AgentToolParameter, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+struct Paging {
+    page_number: u32,
+    page_size: u32,
+}
+
+impl Default for Paging {
+    fn default() -> Self {
+        Self {
+            page_number: 1,
+            page_size: 20,
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(AgentTool, Deserialize)]
+#[serde(tag = "mode", rename_all_fields = "camelCase")]
+enum LookupTool {
+    ByCode {
+        #[tool(required)]
+        airport_code: String,
+    },
+    #[serde(rename_all = "UPPERCASE")]
+    ByCity {
+        #[tool(required)]
+        city_name: String,
+    },
+}
+
+#[test]
+fn schema_uses_wire_names() {
+    assert_eq!(
+        BookFlight::tool_schema_json(),
+        json!({
+            "type": "object",
+            "properties": {
+                "departureAirport": { "type": "string" },
+                "to": { "type": "string" },
+                "seatClass": { "type": "string" },
+                "passengerCount": { "type": "integer" },
+                "flexibleDates": { "type": "boolean" },
+                "type": { "type": "string" },
+            },
+            "required": ["departureAirport", "to"],
+        })
+    );
+    assert_eq!(
+        Paging::parameter_schema_json(),
+        json!({
+            "type": "object",
+            "properties": {
+                "page-number": { "type": "integer" },
+                "page-size": { "type": "integer" },
+            },
+        })
+    );
+}
+
+#[test]
+fn enum_fields_follow_rename_rules() {
+    let schema = LookupTool::tool_schema_json();
+    assert_eq!(
+        schema["oneOf"][0]["properties"]["airportCode"],
+        json!({ "type": "string" })
+    );
+    assert_eq!(
+        schema["oneOf"][0]["required"],
+        json!(["mode", "airportCode"])
+    );
+    assert_eq!(
+        schema["oneOf"][1]["properties"]["CITY_NAME"],
+        json!({ "type": "string" })
+    );
+}
+
+#[test]
+fn advertised_input_deserializes() {
+    let input = json!({
+        "departureAirport": "AMS",
+        "to": "LIS",
+        "seatClass": "economy",
+        "pax": 2,
+        "type": "return",
+    });
+    let parsed: BookFlight = serde_json::from_value(input).expect("parseable");
+    assert_eq!(
+        parsed,
+        BookFlight {
+            departure_airport: "AMS".to_string(),
+            arrival_airport: "LIS".to_string(),
+            seat: Some("economy".to_string()),
+            passenger_count: Some(2),
+            flexible_dates: false,
+            booking_ref: None,
+            price: None,
+            r#type: Some("return".to_string()),
+        }
+    );
+}
+
+#[test]
+fn partial_view_reads_wire_names_and_aliases() {
+    let partial = BookFlight::parse_partial(r#"{"to": "LIS", "travellers": 3, "seatCl"#);
+    assert_eq!(partial.arrival_airport.as_deref(), Some("LIS"));
+    assert_eq!(partial.passenger_count, Some(3));
+    assert_eq!(partial.seat, None);
+}
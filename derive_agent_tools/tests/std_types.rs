@@ -0,0 +1,88 @@
+use std::collections::{BTreeSet, HashSet, VecDeque};
+use std::net::IpAddr;
+
+use derive_agent_tools::schema::SchemaType;
+use derive_agent_tools::{AgentTool, Schema};
+use serde::Deserialize;
+use serde_json::json;
+
+fn ip_schema() -> Schema {
+    let mut schema = Schema::typed(SchemaType::String);
+    schema.constraints.format = Some("ipv4".to_string());
+    schema
+}
+
+#[derive(AgentTool, Deserialize)]
+#[allow(dead_code)]
+struct Route {
+    tags: HashSet<String>,
+    hops: BTreeSet<u32>,
+    queue: VecDeque<String>,
+    origin: (f64, f64),
+    mask: [u8; 4],
+    #[tool(schema = ip_schema, description = "Gateway address")]
+    gateway: IpAddr,
+    #[tool(schema = ip_schema)]
+    fallback: Option<IpAddr>,
+}
+
+#[test]
+fn std_collections_tuples_and_arrays() {
+    let schema = Route::tool_schema_json();
+    let props = &schema["properties"];
+
+    let strings = json!({ "type": "array", "items": { "type": "string" } });
+    assert_eq!(props["tags"], strings);
+    assert_eq!(props["queue"], strings);
+    assert_eq!(
+        props["hops"],
+        json!({ "type": "array", "items": { "type": "integer" } })
+    );
+    assert_eq!(
+        props["origin"],
+        json!({
+            "type": "array",
+            "prefixItems": [{ "type": "number" }, { "type": "number" }],
+            "minItems": 2,
+            "maxItems": 2,
+        })
+    );
+    assert_eq!(
+        props["mask"],
+        json!({
+            "type": "array",
+            "items": { "type": "integer" },
+            "minItems": 4,
+            "maxItems": 4,
+        })
+    );
+}
+
+#[test]
+fn schema_override_covers_foreign_types() {
+    let schema = Route::tool_schema_json();
+    assert_eq!(
+        schema["properties"]["gateway"],
+        json!({ "type": "string", "format": "ipv4", "description": "Gateway address" })
+    );
+    assert_eq!(
+        schema["properties"]["fallback"],
+        json!({ "type": "string", "format": "ipv4" })
+    );
+    assert_eq!(
+        schema["required"],
+        json!(["tags", "hops", "queue", "origin", "mask", "gateway"])
+    );
+
+    let route: Route = serde_json::from_value(json!({
+        "tags": ["a"],
+        "hops": [1, 2],
+        "queue": [],
+        "origin": [1.5, 2.5],
+        "mask": [255, 255, 255, 0],
+        "gateway": "10.0.0.1",
+    }))
+    .expect("parseable");
+    assert_eq!(route.origin, (1.5, 2.5));
+    assert_eq!(route.gateway, "10.0.0.1".parse::<IpAddr>().unwrap());
+}
@@ -0,0 +1,124 @@
+#![cfg(all(feature = "bedrock", feature = "serde-json"))]
+
+use aws_sdk_bedrockruntime::types::{
+    ContentBlockDelta, ContentBlockDeltaEvent, ContentBlockStart, ContentBlockStartEvent,
+    ContentBlockStopEvent, ConverseStreamOutput, ToolUseBlockDelta, ToolUseBlockStart,
+};
+use aws_smithy_types::Document;
+use derive_agent_tools::AgentTool;
+use derive_agent_tools::bedrock::{ToolUseAccumulator, ToolUseError};
+use serde::Deserialize;
+
+#[derive(Debug, PartialEq, AgentTool, Deserialize)]
+struct WeatherTool {
+    #[tool(required)]
+    city: String,
+    days: Option<u8>,
+}
+
+#[derive(Debug, PartialEq, AgentTool, Deserialize)]
+struct PingTool {}
+
+fn start_tool(index: i32, id: &str, name: &str) -> ConverseStreamOutput {
+    let start = ToolUseBlockStart::builder()
+        .tool_use_id(id)
+        .name(name)
+        .build()
+        .unwrap();
+    ConverseStreamOutput::ContentBlockStart(
+        ContentBlockStartEvent::builder()
+            .content_block_index(index)
+            .start(ContentBlockStart::ToolUse(start))
+            .build()
+            .unwrap(),
+    )
+}
+
+fn tool_delta(index: i32, input: &str) -> ConverseStreamOutput {
+    let delta = ToolUseBlockDelta::builder().input(input).build().unwrap();
+    ConverseStreamOutput::ContentBlockDelta(
+        ContentBlockDeltaEvent::builder()
+            .content_block_index(index)
+            .delta(ContentBlockDelta::ToolUse(delta))
+            .build()
+            .unwrap(),
+    )
+}
+
+fn text_delta(index: i32, text: &str) -> ConverseStreamOutput {
+    ConverseStreamOutput::ContentBlockDelta(
+        ContentBlockDeltaEvent::builder()
+            .content_block_index(index)
+            .delta(ContentBlockDelta::Text(text.to_string()))
+            .build()
+            .unwrap(),
+    )
+}
+
+fn stop(index: i32) -> ConverseStreamOutput {
+    ConverseStreamOutput::ContentBlockStop(
+        ContentBlockStopEvent::builder()
+            .content_block_index(index)
+            .build()
+            .unwrap(),
+    )
+}
+
+#[test]
+fn accumulates_interleaved_tool_uses() {
+    let mut acc = ToolUseAccumulator::new();
+    let events = [
+        text_delta(0, "Let me check."),
+        stop(0),
+        start_tool(1, "a", "WeatherTool"),
+        start_tool(2, "b", "WeatherTool"),
+        tool_delta(1, "{\"ci"),
+        tool_delta(2, "{\"city\": \"Oslo\", "),
+        tool_delta(1, "ty\": \"Par"),
+        tool_delta(2, "\"days\": 3}"),
+        tool_delta(1, "is\"}"),
+    ];
+    for event in &events {
+        assert!(acc.push_tool::<WeatherTool>(event).is_none());
+    }
+    assert!(acc.is_pending());
+
+    let oslo = acc.push_tool::<WeatherTool>(&stop(2)).unwrap().unwrap();
+    assert_eq!(oslo.tool_use_id, "b");
+    assert_eq!(
+        oslo.input,
+        WeatherTool {
+            city: "Oslo".to_string(),
+            days: Some(3)
+        }
+    );
+
+    let paris = acc.push_tool::<WeatherTool>(&stop(1)).unwrap().unwrap();
+    assert_eq!(paris.tool_use_id, "a");
+    assert_eq!(paris.input.city, "Paris");
+    assert!(!acc.is_pending());
+}
+
+#[test]
+fn empty_input_is_an_empty_object() {
+    let mut acc = ToolUseAccumulator::new();
+    assert!(acc.push(&start_tool(0, "p", "PingTool")).is_none());
+    let block = acc.push(&stop(0)).unwrap().unwrap();
+    assert_eq!(block.name(), "PingTool");
+    assert_eq!(block.input(), &Document::Object(Default::default()));
+    assert_eq!(PingTool::from_tool_use(&block).unwrap().input, PingTool {});
+}
+
+#[test]
+fn reports_invalid_input_and_other_tools() {
+    let mut acc = ToolUseAccumulator::new();
+    acc.push(&start_tool(0, "x", "WeatherTool"));
+    acc.push(&tool_delta(0, "{\"city\": "));
+    let err = acc.push_tool::<WeatherTool>(&stop(0)).unwrap().unwrap_err();
+    assert!(matches!(err, ToolUseError::InvalidInput { .. }));
+    assert_eq!(err.tool_use_id(), "x");
+
+    acc.push(&start_tool(1, "y", "PingTool"));
+    let err = acc.push_tool::<WeatherTool>(&stop(1)).unwrap().unwrap_err();
+    assert!(matches!(err, ToolUseError::NameMismatch { .. }));
+}
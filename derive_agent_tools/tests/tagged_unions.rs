@@ -0,0 +1,206 @@
+use derive_agent_tools::{AgentTool, AgentToolParameter};
+use serde::Deserialize;
+use serde_json::json;
+
+#[allow(dead_code)]
+#[derive(AgentToolParameter, Deserialize)]
+struct Point {
+    #[tool(required)]
+    x: f64,
+    #[tool(required)]
+    y: f64,
+}
+
+#[allow(dead_code)]
+#[derive(AgentToolParameter, Deserialize)]
+#[serde(tag = "shape", rename_all = "lowercase")]
+enum Internal {
+    Circle {
+        #[tool(required)]
+        radius: f64,
+    },
+    #[tool(description = "A single point")]
+    Dot(Point),
+    Empty,
+}
+
+#[allow(dead_code)]
+#[derive(AgentToolParameter, Deserialize)]
+#[serde(tag = "t", content = "c")]
+enum Adjacent {
+    Text(String),
+    Nothing,
+}
+
+#[allow(dead_code)]
+#[derive(AgentToolParameter, Deserialize)]
+enum External {
+    Id(u64),
+    Named {
+        #[tool(required)]
+        name: String,
+    },
+    Anonymous,
+}
+
+#[allow(dead_code)]
+#[derive(AgentToolParameter, Deserialize)]
+#[serde(untagged)]
+enum Untagged {
+    Number(f64),
+    Words(Vec<String>),
+}
+
+#[derive(Debug, PartialEq, AgentTool, Deserialize)]
+#[tool(name = "search", description = "Search by id or by query")]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum SearchTool {
+    #[tool(description = "Look up a single record")]
+    ById {
+        #[tool(required)]
+        id: String,
+    },
+    ByQuery {
+        #[tool(required)]
+        query: String,
+        limit: Option<u32>,
+    },
+}
+
+#[test]
+fn internally_tagged() {
+    assert_eq!(
+        Internal::parameter_schema_json(),
+        json!({
+            "type": "object",
+            "oneOf": [
+                {
+                    "type": "object",
+                    "properties": {
+                        "shape": { "type": "string", "enum": ["circle"] },
+                        "radius": { "type": "number" },
+                    },
+                    "required": ["shape", "radius"],
+                },
+                {
+                    "type": "object",
+                    "description": "A single point",
+                    "properties": {
+                        "shape": { "type": "string", "enum": ["dot"] },
+                        "x": { "type": "number" },
+                        "y": { "type": "number" },
+                    },
+                    "required": ["shape", "x", "y"],
+                },
+                {
+                    "type": "object",
+                    "properties": { "shape": { "type": "string", "enum": ["empty"] } },
+                    "required": ["shape"],
+                },
+            ],
+        })
+    );
+}
+
+#[test]
+fn adjacently_tagged() {
+    assert_eq!(
+        Adjacent::parameter_schema_json(),
+        json!({
+            "type": "object",
+            "oneOf": [
+                {
+                    "type": "object",
+                    "properties": {
+                        "t": { "type": "string", "enum": ["Text"] },
+                        "c": { "type": "string" },
+                    },
+                    "required": ["t", "c"],
+                },
+                {
+                    "type": "object",
+                    "properties": { "t": { "type": "string", "enum": ["Nothing"] } },
+                    "required": ["t"],
+                },
+            ],
+        })
+    );
+}
+
+#[test]
+fn externally_tagged() {
+    assert_eq!(
+        External::parameter_schema_json(),
+        json!({
+            "oneOf": [
+                {
+                    "type": "object",
+                    "properties": { "Id": { "type": "integer" } },
+                    "required": ["Id"],
+                },
+                {
+                    "type": "object",
+                    "properties": {
+                        "Named": {
+                            "type": "object",
+                            "properties": { "name": { "type": "string" } },
+                            "required": ["name"],
+                        },
+                    },
+                    "required": ["Named"],
+                },
+                { "type": "string", "enum": ["Anonymous"] },
+            ],
+        })
+    );
+}
+
+#[test]
+fn untagged() {
+    assert_eq!(
+        Untagged::parameter_schema_json(),
+        json!({
+            "anyOf": [
+                { "type": "number" },
+                { "type": "array", "items": { "type": "string" } },
+            ],
+        })
+    );
+}
+
+#[test]
+fn enum_tools() {
+    let schema = SearchTool::tool_schema_json();
+    assert_eq!(schema["type"], "object");
+    let branches = schema["oneOf"].as_array().expect("oneOf branches");
+    assert_eq!(branches.len(), 2);
+    assert_eq!(branches[0]["description"], "Look up a single record");
+    assert_eq!(
+        branches[0]["properties"]["kind"],
+        json!({ "type": "string", "enum": ["by_id"] })
+    );
+    assert_eq!(branches[1]["required"], json!(["kind", "query"]));
+}
+
+#[test]
+#[cfg(feature = "bedrock")]
+fn enum_tools_parse_documents() {
+    use std::collections::HashMap;
+
+    use aws_smithy_types::Document;
+
+    let mut input = HashMap::new();
+    input.insert("kind".to_string(), Document::String("by_query".to_string()));
+    input.insert("query".to_string(), Document::String("rust".to_string()));
+    let parsed = SearchTool::from_document(&Document::Object(input)).expect("parseable");
+    assert_eq!(
+        parsed,
+        SearchTool::ByQuery {
+            query: "rust".to_string(),
+            limit: None
+        }
+    );
+
+    let spec = SearchTool::tool_spec();
+    assert_eq!(spec.name(), "search");
+}
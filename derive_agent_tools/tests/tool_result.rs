@@ -0,0 +1,106 @@
+#![cfg(all(feature = "bedrock", feature = "serde-json"))]
+
+use std::collections::HashMap;
+
+use aws_sdk_bedrockruntime::types::{ToolResultContentBlock, ToolResultStatus};
+use aws_smithy_types::{Document, Number};
+use derive_agent_tools::AgentTool;
+use derive_agent_tools::bedrock::tool_result;
+use derive_agent_tools::document::to_document;
+use serde::{Deserialize, Serialize};
+
+#[allow(dead_code)]
+#[derive(AgentTool, Deserialize)]
+struct WeatherTool {
+    #[tool(required)]
+    city: String,
+}
+
+#[derive(Serialize)]
+struct Forecast {
+    city: String,
+    temperature: f64,
+    offset: i32,
+    tags: Vec<&'static str>,
+}
+
+#[test]
+fn serialize_to_document() {
+    let forecast = Forecast {
+        city: "Paris".to_string(),
+        temperature: 21.5,
+        offset: -2,
+        tags: vec!["sunny"],
+    };
+    let expected = Document::Object(HashMap::from([
+        ("city".to_string(), Document::String("Paris".to_string())),
+        (
+            "temperature".to_string(),
+            Document::Number(Number::Float(21.5)),
+        ),
+        ("offset".to_string(), Document::Number(Number::NegInt(-2))),
+        (
+            "tags".to_string(),
+            Document::Array(vec![Document::String("sunny".to_string())]),
+        ),
+    ]));
+    assert_eq!(to_document(&forecast).unwrap(), expected);
+    assert_eq!(
+        to_document(&7u8).unwrap(),
+        Document::Number(Number::PosInt(7))
+    );
+}
+
+#[test]
+fn object_output_is_json_content() {
+    let forecast = Forecast {
+        city: "Paris".to_string(),
+        temperature: 21.5,
+        offset: 1,
+        tags: vec![],
+    };
+    let block = WeatherTool::tool_result("tooluse_1", Ok::<_, String>(&forecast));
+    assert_eq!(block.tool_use_id(), "tooluse_1");
+    assert_eq!(block.status(), None);
+    assert_eq!(
+        block.content(),
+        &[ToolResultContentBlock::Json(
+            to_document(&forecast).unwrap()
+        )]
+    );
+}
+
+#[test]
+fn other_outputs_are_text_content() {
+    let block = tool_result("a", Ok::<_, String>("sunny"));
+    assert_eq!(
+        block.content(),
+        &[ToolResultContentBlock::Text("sunny".to_string())]
+    );
+
+    let block = tool_result("b", Ok::<_, String>(vec![1, 2]));
+    assert_eq!(
+        block.content(),
+        &[ToolResultContentBlock::Text("[1,2]".to_string())]
+    );
+}
+
+#[test]
+fn errors_have_error_status() {
+    let block = WeatherTool::tool_result("tooluse_2", Err::<(), _>("city not found"));
+    assert_eq!(block.status(), Some(&ToolResultStatus::Error));
+    assert_eq!(
+        block.content(),
+        &[ToolResultContentBlock::Text("city not found".to_string())]
+    );
+
+    let unserializable = HashMap::from([((1, 2), "tuple keys")]);
+    let block = tool_result("tooluse_3", Ok::<_, String>(unserializable));
+    assert_eq!(block.status(), Some(&ToolResultStatus::Error));
+    assert!(
+        block.content()[0]
+            .as_text()
+            .unwrap()
+            .starts_with("failed to serialize tool output")
+    );
+}
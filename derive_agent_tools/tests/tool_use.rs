@@ -0,0 +1,120 @@
+#![cfg(all(feature = "bedrock", feature = "serde-json"))]
+
+use std::collections::HashMap;
+
+use aws_sdk_bedrockruntime::types::{
+    ContentBlock, ConversationRole, ConverseOutput, Message, ToolResultStatus, ToolUseBlock,
+};
+use aws_smithy_types::{Document, Number};
+use derive_agent_tools::AgentTool;
+use derive_agent_tools::bedrock::{ToolInvocation, ToolUseError, tool_uses};
+use serde::Deserialize;
+
+#[derive(Debug, PartialEq, AgentTool, Deserialize)]
+#[tool(name = "get_weather")]
+struct WeatherTool {
+    #[tool(required)]
+    city: String,
+}
+
+#[derive(Debug, PartialEq, AgentTool, Deserialize)]
+struct TimeTool {
+    #[tool(required)]
+    zone: String,
+}
+
+fn tool_use(id: &str, name: &str, input: &[(&str, Document)]) -> ToolUseBlock {
+    let input = input
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.clone()))
+        .collect::<HashMap<_, _>>();
+    ToolUseBlock::builder()
+        .tool_use_id(id)
+        .name(name)
+        .input(Document::Object(input))
+        .build()
+        .unwrap()
+}
+
+fn city(name: &str) -> [(&'static str, Document); 1] {
+    [("city", Document::String(name.to_string()))]
+}
+
+#[test]
+fn parses_matching_tool_use() {
+    let block = tool_use("tooluse_1", "get_weather", &city("Paris"));
+    assert_eq!(
+        WeatherTool::from_tool_use(&block).unwrap(),
+        ToolInvocation {
+            tool_use_id: "tooluse_1".to_string(),
+            input: WeatherTool {
+                city: "Paris".to_string()
+            },
+        }
+    );
+}
+
+#[test]
+fn rejects_other_tools_and_bad_input() {
+    let block = tool_use("tooluse_2", "TimeTool", &city("Paris"));
+    let err = WeatherTool::from_tool_use(&block).unwrap_err();
+    assert!(matches!(
+        &err,
+        ToolUseError::NameMismatch { expected: "get_weather", found, .. } if found == "TimeTool"
+    ));
+    assert_eq!(err.tool_use_id(), "tooluse_2");
+
+    let block = tool_use(
+        "tooluse_3",
+        "get_weather",
+        &[("city", Document::Number(Number::PosInt(1)))],
+    );
+    let err = WeatherTool::from_tool_use(&block).unwrap_err();
+    assert!(matches!(err, ToolUseError::InvalidInput { .. }));
+    assert_eq!(err.tool_use_id(), "tooluse_3");
+}
+
+#[test]
+fn extracts_tool_uses_from_converse_output() {
+    let message = Message::builder()
+        .role(ConversationRole::Assistant)
+        .content(ContentBlock::Text("Checking.".to_string()))
+        .content(ContentBlock::ToolUse(tool_use(
+            "a",
+            "get_weather",
+            &city("Paris"),
+        )))
+        .content(ContentBlock::ToolUse(tool_use(
+            "b",
+            "TimeTool",
+            &[("zone", Document::String("CET".to_string()))],
+        )))
+        .content(ContentBlock::ToolUse(tool_use("c", "get_weather", &[])))
+        .content(ContentBlock::ToolUse(tool_use(
+            "d",
+            "get_weather",
+            &city("Oslo"),
+        )))
+        .build()
+        .unwrap();
+    let output = ConverseOutput::Message(message);
+
+    let calls = tool_uses::<WeatherTool>(&output);
+    assert_eq!(calls.len(), 3);
+    assert_eq!(calls[0].as_ref().unwrap().input.city, "Paris");
+    assert_eq!(calls[1].as_ref().unwrap_err().tool_use_id(), "c");
+    assert_eq!(calls[2].as_ref().unwrap().tool_use_id, "d");
+
+    let times = tool_uses::<TimeTool>(&output);
+    assert_eq!(times.len(), 1);
+    assert_eq!(times[0].as_ref().unwrap().input.zone, "CET");
+}
+
+#[test]
+fn invocation_builds_matching_result() {
+    let block = tool_use("tooluse_4", "get_weather", &city("Paris"));
+    let call = WeatherTool::from_tool_use(&block).unwrap();
+    let result = call.result(Err::<(), _>("unavailable"));
+    assert_eq!(result.tool_use_id(), "tooluse_4");
+    assert_eq!(result.status(), Some(&ToolResultStatus::Error));
+}
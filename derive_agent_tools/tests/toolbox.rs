@@ -0,0 +1,102 @@
+use derive_agent_tools::{AgentTool, ToolBox, ToolBoxError};
+use serde::Deserialize;
+use serde_json::json;
+
+#[derive(AgentTool, Deserialize)]
+#[tool(name = "weather", description = "Get the weather")]
+struct WeatherTool {
+    #[tool(required)]
+    city: String,
+}
+
+#[derive(AgentTool, Deserialize)]
+#[tool(name = "time")]
+struct TimeTool {
+    zone: Option<String>,
+}
+
+fn toolbox() -> ToolBox<String> {
+    let mut tools = ToolBox::new();
+    tools
+        .register(|args: WeatherTool| format!("sunny in {}", args.city))
+        .register(|args: TimeTool| format!("noon in {}", args.zone.as_deref().unwrap_or("UTC")));
+    tools
+}
+
+#[test]
+fn dispatches_json_by_name() {
+    let tools = toolbox();
+    assert_eq!(tools.names().collect::<Vec<_>>(), ["weather", "time"]);
+    assert_eq!(
+        tools
+            .call_json("weather", &json!({ "city": "Oslo" }))
+            .unwrap(),
+        "sunny in Oslo"
+    );
+    assert_eq!(tools.call_json("time", &json!({})).unwrap(), "noon in UTC");
+}
+
+#[test]
+fn reports_unknown_tools_and_bad_input() {
+    let tools = toolbox();
+    assert!(matches!(
+        tools.call_json("stocks", &json!({})),
+        Err(ToolBoxError::UnknownTool(name)) if name == "stocks"
+    ));
+    let err = tools
+        .call_json("weather", &json!({ "town": "Oslo" }))
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        ToolBoxError::InvalidInput {
+            tool: "weather",
+            ..
+        }
+    ));
+    assert!(err.to_string().contains("city"));
+}
+
+#[test]
+fn lists_schemas() {
+    let schemas = toolbox().json_schemas();
+    assert_eq!(schemas.len(), 2);
+    assert_eq!(schemas[0], ("weather", WeatherTool::tool_schema_json()));
+    assert_eq!(schemas[1], ("time", TimeTool::tool_schema_json()));
+}
+
+#[test]
+#[cfg(feature = "bedrock")]
+fn bedrock_configuration_and_documents() {
+    use std::collections::HashMap;
+
+    use aws_sdk_bedrockruntime::types::Tool;
+    use aws_smithy_types::Document;
+
+    let tools = toolbox();
+    let config = tools.tool_configuration();
+    let names: Vec<_> = config
+        .tools()
+        .iter()
+        .map(|tool| match tool {
+            Tool::ToolSpec(spec) => spec.name(),
+            _ => panic!("expected a tool spec"),
+        })
+        .collect();
+    assert_eq!(names, ["weather", "time"]);
+
+    let mut input = HashMap::new();
+    input.insert("city".to_string(), Document::String("Lima".to_string()));
+    assert_eq!(
+        tools
+            .call_document("weather", &Document::Object(input))
+            .unwrap(),
+        "sunny in Lima"
+    );
+    assert!(matches!(
+        tools.call_document("weather", &Document::Null),
+        Err(ToolBoxError::InvalidInput {
+            tool: "weather",
+            ..
+        })
+    ));
+}
@@ -0,0 +1,63 @@
+use derive_agent_tools::{AgentTool, DynAgentTool, ToolType};
+use serde::Deserialize;
+
+#[allow(dead_code)]
+#[derive(AgentTool, Deserialize)]
+#[tool(name = "lookup", description = "Look something up")]
+struct LookupTool {
+    #[tool(required)]
+    query: String,
+}
+
+#[allow(dead_code)]
+#[derive(AgentTool, Deserialize)]
+struct PingTool {
+    count: Option<u32>,
+}
+
+fn describe<T: AgentTool>() -> (&'static str, Option<&'static str>) {
+    (T::tool_name(), T::tool_description())
+}
+
+#[test]
+fn generic_over_tools() {
+    assert_eq!(
+        describe::<LookupTool>(),
+        ("lookup", Some("Look something up"))
+    );
+    assert_eq!(describe::<PingTool>(), ("PingTool", None));
+}
+
+#[test]
+fn tools_behind_dyn() {
+    let tools: Vec<Box<dyn DynAgentTool>> = vec![
+        Box::new(ToolType::<LookupTool>::new()),
+        Box::new(ToolType::<PingTool>::new()),
+    ];
+    let names: Vec<_> = tools.iter().map(|t| t.name()).collect();
+    assert_eq!(names, ["lookup", "PingTool"]);
+
+    #[cfg(feature = "serde-json")]
+    assert_eq!(tools[0].schema_json(), LookupTool::tool_schema_json());
+    #[cfg(feature = "bedrock")]
+    assert_eq!(tools[0].spec(), LookupTool::tool_spec());
+}
+
+#[test]
+#[cfg(feature = "bedrock")]
+fn parses_through_trait() {
+    use std::collections::HashMap;
+
+    use aws_smithy_types::Document;
+
+    let mut input = HashMap::new();
+    input.insert("query".to_string(), Document::String("rust".to_string()));
+    let parsed = LookupTool::from_document(&Document::Object(input)).expect("parseable");
+    assert_eq!(parsed.query, "rust");
+
+    let err: derive_agent_tools::ToolParseError = match LookupTool::from_document(&Document::Null) {
+        Ok(_) => panic!("null is not a valid input"),
+        Err(err) => err,
+    };
+    assert!(!err.to_string().is_empty());
+}
@@ -0,0 +1,10 @@
+use derive_agent_tools::AgentTool;
+use serde::Deserialize;
+
+#[derive(AgentTool, Deserialize)]
+#[tool(desc = "Search the catalog")]
+struct Search {
+    query: String,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use derive_agent_tools::AgentTool;
+use serde::Deserialize;
+
+#[derive(AgentTool, Deserialize)]
+struct Search {
+    #[tool(description = "What to look for")]
+    #[tool(description = "The query")]
+    query: String,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use derive_agent_tools::AgentTool;
+use serde::Deserialize;
+
+#[derive(AgentTool, Deserialize)]
+#[tool(name = "search", required)]
+struct Search {
+    query: String,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use derive_agent_tools::AgentToolParameter;
+use serde::Deserialize;
+
+#[derive(AgentToolParameter, Deserialize)]
+enum Unit {
+    #[tool(optional)]
+    Celsius,
+    Fahrenheit,
+}
+
+fn main() {}
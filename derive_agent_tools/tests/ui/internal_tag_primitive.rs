@@ -0,0 +1,11 @@
+use derive_agent_tools::AgentToolParameter;
+use serde::Deserialize;
+
+#[derive(AgentToolParameter, Deserialize)]
+#[serde(tag = "kind")]
+enum Contact {
+    Name(String),
+    Address { street: String },
+}
+
+fn main() {}
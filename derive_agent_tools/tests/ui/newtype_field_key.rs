@@ -0,0 +1,7 @@
+use derive_agent_tools::AgentToolParameter;
+use serde::Deserialize;
+
+#[derive(AgentToolParameter, Deserialize)]
+struct Email(#[tool(descriptoin = "An email address")] String);
+
+fn main() {}
@@ -0,0 +1,10 @@
+use derive_agent_tools::AgentToolParameter;
+use serde::Deserialize;
+
+#[derive(AgentToolParameter, Deserialize)]
+enum Amount {
+    Cents(#[tool(requried)] i32),
+    Unknown,
+}
+
+fn main() {}
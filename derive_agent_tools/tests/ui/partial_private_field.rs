@@ -0,0 +1,17 @@
+mod search {
+    use derive_agent_tools::AgentTool;
+    use serde::Deserialize;
+
+    #[derive(AgentTool, Deserialize)]
+    pub struct SearchTool {
+        pub query: String,
+        cursor: Option<u64>,
+    }
+}
+
+fn main() {
+    use derive_agent_tools::PartialAgentTool;
+
+    let partial = search::SearchTool::parse_partial("{}");
+    let _ = partial.cursor;
+}
@@ -0,0 +1,12 @@
+use derive_agent_tools::AgentTool;
+use serde::Deserialize;
+
+#[derive(AgentTool, Deserialize)]
+struct Search {
+    query: String,
+    #[tool(required)]
+    #[serde(default)]
+    limit: u32,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use derive_agent_tools::AgentTool;
+use serde::Deserialize;
+
+#[derive(AgentTool, Deserialize)]
+struct Search {
+    #[tool(title = "Query")]
+    query: String,
+}
+
+fn main() {}
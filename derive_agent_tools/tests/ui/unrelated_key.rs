@@ -0,0 +1,8 @@
+use derive_agent_tools::AgentToolParameter;
+use serde::Deserialize;
+
+#[derive(AgentToolParameter, Deserialize)]
+#[tool(format = "date")]
+struct Day(String);
+
+fn main() {}
@@ -0,0 +1,47 @@
+//! A crate using the derives the way a downstream user would, without
+//! features of its own that mirror those of `derive_agent_tools`.
+
+use derive_agent_tools::{AgentTool, AgentToolParameter};
+use serde::Deserialize;
+
+#[derive(Debug, PartialEq, AgentToolParameter, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Unit {
+    Celsius,
+    Fahrenheit,
+}
+
+#[derive(Debug, PartialEq, AgentTool, Deserialize)]
+#[tool(name = "weather", description = "Get the weather", handler = forecast)]
+pub struct WeatherTool {
+    #[tool(required, description = "City name")]
+    pub city: String,
+    pub unit: Option<Unit>,
+}
+
+fn forecast(tool: WeatherTool) -> Result<String, String> {
+    Ok(format!("Sunny in {}", tool.city))
+}
+
+/// Implemented by hand, without the feature-gated methods, so that enabling
+/// more facade features must keep it compiling.
+#[derive(Debug, PartialEq, Deserialize)]
+pub struct PingTool {
+    pub host: String,
+}
+
+impl AgentTool for PingTool {
+    type ParseError = derive_agent_tools::ToolParseError;
+
+    fn tool_name() -> &'static str {
+        "ping"
+    }
+
+    fn tool_description() -> Option<&'static str> {
+        None
+    }
+
+    fn tool_schema() -> derive_agent_tools::Schema {
+        derive_agent_tools::Schema::object(Vec::new())
+    }
+}
@@ -0,0 +1,72 @@
+use derive_agent_tools::{AgentTool, PartialAgentTool, SyncAgentToolHandler};
+use derive_agent_tools_consumer::WeatherTool;
+use serde_json::json;
+
+#[test]
+fn json_items_follow_facade_features() {
+    assert_eq!(
+        WeatherTool::tool_schema_json(),
+        json!({
+            "type": "object",
+            "properties": {
+                "city": { "type": "string", "description": "City name" },
+                "unit": { "type": "string", "enum": ["celsius", "fahrenheit"] },
+            },
+            "required": ["city"],
+        })
+    );
+
+    let partial = WeatherTool::parse_partial(r#"{"city": "Par"#);
+    assert_eq!(partial.city.as_deref(), Some("Par"));
+
+    let tool = WeatherTool {
+        city: "Paris".to_string(),
+        unit: None,
+    };
+    assert_eq!(tool.call_sync(&()), Ok(json!("Sunny in Paris")));
+}
+
+#[test]
+#[cfg(feature = "aws")]
+fn bedrock_items_follow_facade_features() {
+    use std::collections::HashMap;
+
+    use aws_smithy_types::Document;
+    use derive_agent_tools_consumer::Unit;
+
+    let spec = WeatherTool::tool_spec();
+    assert_eq!(spec.name(), "weather");
+
+    let input = Document::Object(HashMap::from([
+        ("city".to_string(), Document::String("Oslo".to_string())),
+        ("unit".to_string(), Document::String("celsius".to_string())),
+    ]));
+    let parsed = WeatherTool::try_from(&input).expect("parseable");
+    assert_eq!(
+        parsed,
+        WeatherTool {
+            city: "Oslo".to_string(),
+            unit: Some(Unit::Celsius),
+        }
+    );
+}
+
+#[test]
+#[cfg(feature = "aws")]
+fn hand_written_tools_parse_documents_by_default() {
+    use std::collections::HashMap;
+
+    use aws_smithy_types::Document;
+    use derive_agent_tools_consumer::PingTool;
+
+    let input = Document::Object(HashMap::from([(
+        "host".to_string(),
+        Document::String("example.com".to_string()),
+    )]));
+    assert_eq!(
+        PingTool::from_document(&input).expect("parseable"),
+        PingTool {
+            host: "example.com".to_string(),
+        }
+    );
+}
@@ -0,0 +1,175 @@
+//! Reading `///` doc comments as fallback descriptions.
+//!
+//! Rustdoc stores each comment line as its own `#[doc = "..."]` attribute
+//! (block comments as one attribute spanning several lines). The lines are
+//! joined, their common indentation removed and the result trimmed, so the
+//! description matches what rustdoc renders from.
+
+use syn::{Attribute, Expr, ExprLit, Lit, Meta};
+
+/// How doc comments are turned into descriptions.
+#[derive(Clone, Copy, Default)]
+pub(crate) enum DocStyle {
+    /// The Markdown source, line breaks included.
+    #[default]
+    Markdown,
+    /// `#[tool(strip_markdown)]`: plain text, with soft-wrapped lines joined.
+    Plain,
+}
+
+impl DocStyle {
+    /// The description written in `attrs`' doc comments, if any.
+    pub(crate) fn description(self, attrs: &[Attribute]) -> Option<String> {
+        let text = doc_text(attrs)?;
+        let text = match self {
+            Self::Markdown => text,
+            Self::Plain => strip_markdown(&text),
+        };
+        (!text.is_empty()).then_some(text)
+    }
+}
+
+fn doc_text(attrs: &[Attribute]) -> Option<String> {
+    let mut lines = Vec::new();
+    for attr in attrs {
+        if !attr.path().is_ident("doc") {
+            continue;
+        }
+        if let Meta::NameValue(nv) = &attr.meta
+            && let Expr::Lit(ExprLit {
+                lit: Lit::Str(lit), ..
+            }) = &nv.value
+        {
+            lines.extend(lit.value().split('\n').map(str::to_string));
+        }
+    }
+    if lines.is_empty() {
+        return None;
+    }
+
+    let indent = lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    let text = lines
+        .iter()
+        .map(|line| line.get(indent..).unwrap_or("").trim_end())
+        .collect::<Vec<_>>()
+        .join("\n");
+    Some(text.trim().to_string())
+}
+
+/// Reduces Markdown to plain text: code fences, heading and quote markers,
+/// emphasis, backticks and link targets are dropped, and the lines of a
+/// paragraph are joined. List items keep their own lines.
+fn strip_markdown(text: &str) -> String {
+    let mut out = String::new();
+    let mut in_fence = false;
+    let mut paragraph_open = false;
+    for line in text.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            paragraph_open = false;
+            continue;
+        }
+        if in_fence {
+            if !out.is_empty() {
+                out.push_str(if paragraph_open { "\n" } else { "\n\n" });
+            }
+            out.push_str(line);
+            paragraph_open = true;
+            continue;
+        }
+        if trimmed.is_empty() {
+            paragraph_open = false;
+            continue;
+        }
+
+        let content = trimmed.trim_start_matches('>').trim_start();
+        let content = match content.trim_start_matches('#') {
+            rest if rest.len() < content.len() && rest.starts_with(' ') => rest.trim_start(),
+            _ => content,
+        };
+        let (bullet, content) = match content.split_once(' ') {
+            Some(("-" | "*" | "+", rest)) => ("- ", rest),
+            Some((marker, rest))
+                if marker
+                    .strip_suffix('.')
+                    .is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit())) =>
+            {
+                (&content[..marker.len() + 1], rest)
+            }
+            _ => ("", content),
+        };
+        let content = strip_inline(content);
+
+        if paragraph_open && bullet.is_empty() {
+            out.push(' ');
+        } else if !out.is_empty() {
+            out.push_str(if paragraph_open { "\n" } else { "\n\n" });
+        }
+        out.push_str(bullet);
+        out.push_str(&content);
+        paragraph_open = true;
+    }
+    out.trim().to_string()
+}
+
+/// Drops inline markup from one line. Code spans are kept verbatim without
+/// their backticks.
+fn strip_inline(line: &str) -> String {
+    let mut out = String::new();
+    let mut rest = line;
+    while let Some(ch) = rest.chars().next() {
+        match ch {
+            '`' => {
+                let ticks = rest.len() - rest.trim_start_matches('`').len();
+                let body = &rest[ticks..];
+                match body.find(&rest[..ticks]) {
+                    Some(end) => {
+                        out.push_str(body[..end].trim());
+                        rest = &body[end + ticks..];
+                    }
+                    None => {
+                        out.push_str(&rest[..ticks]);
+                        rest = body;
+                    }
+                }
+            }
+            '[' => match link(rest) {
+                Some((text, len)) => {
+                    out.push_str(&strip_inline(text));
+                    rest = &rest[len..];
+                }
+                None => {
+                    out.push('[');
+                    rest = &rest[1..];
+                }
+            },
+            '*' => rest = &rest[1..],
+            '_' if rest.starts_with("__") => rest = &rest[2..],
+            _ => {
+                out.push(ch);
+                rest = &rest[ch.len_utf8()..];
+            }
+        }
+    }
+    out
+}
+
+/// Parses `[text](url)`, `[text][ref]` or an intra-doc `[text]` at the start
+/// of `s`, returning the text and the length of the whole link.
+fn link(s: &str) -> Option<(&str, usize)> {
+    let close = s.find(']')?;
+    let text = &s[1..close];
+    let after = &s[close + 1..];
+    let target = match after.chars().next() {
+        Some('(') => after.find(')').map(|end| end + 1),
+        Some('[') => after.find(']').map(|end| end + 1),
+        _ => None,
+    };
+    Some((text, close + 1 + target.unwrap_or(0)))
+}
@@ -3,14 +3,27 @@ extern crate proc_macro;
 use proc_macro::TokenStream;
 use quote::{format_ident, quote};
 use syn::{
-    parse_macro_input, spanned::Spanned, Data, DataStruct, DeriveInput, Fields, LitStr, Type,
+    Data, DataEnum, DataStruct, DeriveInput, Fields, FieldsNamed, LitStr, Variant,
+    parse_macro_input, spanned::Spanned,
 };
 
+mod docs;
+mod schema;
+mod serde_attrs;
+mod tool_attrs;
+
+use docs::DocStyle;
+use schema::{Branch, Property, SchemaNode};
+use serde_attrs::{EnumRepr, RenameRule, SerdeContainer, SerdeField, SerdeVariant};
+use tool_attrs::{Level, parse_tool_attrs, skip_tool_key, unknown_key};
+
 // Macro entry points -------------------------------------------------------
 //
 // `derive_agent_tool` drives the bulk of the code generation. The macro keeps
 // the expansion self-contained so downstream crates only need the
-// `derive_agent_tools` facade crate.
+// `derive_agent_tools` facade crate. Feature-dependent items are wrapped in
+// `__macro_support::if_serde_json!`/`if_bedrock!`: a `#[cfg(feature = ...)]`
+// in the expansion would test the caller's features, not the facade's.
 
 #[proc_macro_derive(AgentTool, attributes(tool))]
 pub fn derive_agent_tool(input: TokenStream) -> TokenStream {
@@ -34,348 +47,728 @@ pub fn derive_agent_tool_parameter(input: TokenStream) -> TokenStream {
 fn impl_agent_tool(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
     let ident = &input.ident;
 
-    let Data::Struct(DataStruct {
-        fields: Fields::Named(fields),
-        ..
-    }) = &input.data
-    else {
-        return Err(syn::Error::new(
-            input.span(),
-            "AgentTool can only be derived for structs with named fields",
-        ));
-    };
-
-    // Parse struct-level attributes: name, description
+    // Parse struct-level attributes: name, description, title, annotation hints
+    let mut docs = DocStyle::Markdown;
     let mut tool_name: Option<String> = None;
     let mut tool_description: Option<String> = None;
-    for attr in &input.attrs {
-        if !attr.path().is_ident("tool") {
-            continue;
-        }
-        attr.parse_nested_meta(|meta| {
-            if meta.path.is_ident("name") {
-                let lit: LitStr = meta.value()?.parse()?;
-                tool_name = Some(lit.value());
-                Ok(())
-            } else if meta.path.is_ident("description") {
-                let lit: LitStr = meta.value()?.parse()?;
-                tool_description = Some(lit.value());
-                Ok(())
-            } else {
-                Ok(())
+    let mut tool_title: Option<String> = None;
+    let mut read_only: Option<bool> = None;
+    let mut destructive: Option<bool> = None;
+    let mut idempotent: Option<bool> = None;
+    let mut open_world: Option<bool> = None;
+    let mut handler: Option<(syn::Path, bool)> = None;
+    let mut context: Option<syn::Type> = None;
+    parse_tool_attrs(&input.attrs, |meta| {
+        if meta.path.is_ident("name") {
+            let lit: LitStr = meta.value()?.parse()?;
+            tool_name = Some(lit.value());
+            Ok(())
+        } else if meta.path.is_ident("description") {
+            let lit: LitStr = meta.value()?.parse()?;
+            tool_description = Some(lit.value());
+            Ok(())
+        } else if meta.path.is_ident("title") {
+            let lit: LitStr = meta.value()?.parse()?;
+            tool_title = Some(lit.value());
+            Ok(())
+        } else if meta.path.is_ident("read_only") {
+            read_only = Some(parse_flag(&meta)?);
+            Ok(())
+        } else if meta.path.is_ident("destructive") {
+            destructive = Some(parse_flag(&meta)?);
+            Ok(())
+        } else if meta.path.is_ident("idempotent") {
+            idempotent = Some(parse_flag(&meta)?);
+            Ok(())
+        } else if meta.path.is_ident("open_world") {
+            open_world = Some(parse_flag(&meta)?);
+            Ok(())
+        } else if meta.path.is_ident("handler") || meta.path.is_ident("async_handler") {
+            if handler.is_some() {
+                return Err(meta.error("a tool can only have one handler"));
             }
-        })?;
+            let is_async = meta.path.is_ident("async_handler");
+            handler = Some((meta.value()?.parse()?, is_async));
+            Ok(())
+        } else if meta.path.is_ident("context") {
+            context = Some(meta.value()?.parse()?);
+            Ok(())
+        } else if meta.path.is_ident("strip_markdown") {
+            if parse_flag(&meta)? {
+                docs = DocStyle::Plain;
+            }
+            Ok(())
+        } else {
+            Err(unknown_key(&meta, Level::Tool))
+        }
+    })?;
+    if let (Some(context), None) = (&context, &handler) {
+        return Err(syn::Error::new(
+            context.span(),
+            "`context` requires `handler` or `async_handler`",
+        ));
     }
 
+    let (node, partial_impl) = match &input.data {
+        Data::Struct(DataStruct {
+            fields: Fields::Named(fields),
+            ..
+        }) => {
+            let container = SerdeContainer::from_attrs(&input.attrs)?;
+            let fields = parse_named_fields(fields, container.rename_all, container.default, docs)?;
+            let partial_impl = partial_tokens(input, &fields);
+            let properties = fields.into_iter().map(|f| f.property).collect();
+            (SchemaNode::Object(properties), Some(partial_impl))
+        }
+        Data::Enum(data) => (enum_schema(input, data, true, docs)?.0, None),
+        _ => {
+            return Err(syn::Error::new(
+                input.span(),
+                "AgentTool can only be derived for structs with named fields or enums",
+            ));
+        }
+    };
+
+    let tool_description = tool_description.or_else(|| docs.description(&input.attrs));
     let computed_tool_name = tool_name.unwrap_or_else(|| ident.to_string());
     let description_tokens = if let Some(desc) = tool_description {
         quote! { Some(#desc) }
     } else {
         quote! { None::<&'static str> }
     };
+    let title_tokens = if let Some(title) = tool_title {
+        quote! { Some(#title) }
+    } else {
+        quote! { None::<&'static str> }
+    };
+    let hint = |value: Option<bool>| match value {
+        Some(value) => quote! { Some(#value) },
+        None => quote! { None },
+    };
+    let (read_only, destructive, idempotent, open_world) = (
+        hint(read_only),
+        hint(destructive),
+        hint(idempotent),
+        hint(open_world),
+    );
 
-    // Per-field metadata
-    struct FieldMeta {
-        name: String,
-        description: Option<String>,
-        required: bool,
-        json_type: String,
-        items_type: Option<String>,
-    }
+    let schema_meta = schema::meta_tokens(&node);
+    let ms = quote! { ::derive_agent_tools::__macro_support };
 
-    let mut field_metas: Vec<FieldMeta> = Vec::new();
-    for field in fields.named.iter() {
-        let Some(field_ident) = &field.ident else {
-            continue;
-        };
-        let field_name = field_ident.to_string();
-
-        let mut required = false;
-        let mut description: Option<String> = None;
-        for attr in &field.attrs {
-            if !attr.path().is_ident("tool") {
-                continue;
-            }
-            attr.parse_nested_meta(|meta| {
-                if meta.path.is_ident("required") {
-                    required = true;
-                    Ok(())
-                } else if meta.path.is_ident("description") {
-                    let lit: LitStr = meta.value()?.parse()?;
-                    description = Some(lit.value());
-                    Ok(())
-                } else {
-                    Ok(())
-                }
-            })?;
+    // Implementations
+    let err_ident = format_ident!("{}AgentToolParseError", ident);
+    let tool_impl = quote! {
+        impl #ident {
+            const __AGENT_TOOL_NAME: &'static str = #computed_tool_name;
+            const __AGENT_TOOL_DESCRIPTION: Option<&'static str> = #description_tokens;
+            const __AGENT_TOOL_SCHEMA: #ms::SchemaMeta = #schema_meta;
         }
 
-        let (json_type, items_type) = infer_json_type(&field.ty);
+        impl ::derive_agent_tools::AgentTool for #ident {
+            type ParseError = ::derive_agent_tools::ToolParseError;
 
-        field_metas.push(FieldMeta {
-            name: field_name,
-            description,
-            required,
-            json_type,
-            items_type,
-        });
-    }
+            fn tool_name() -> &'static str {
+                Self::__AGENT_TOOL_NAME
+            }
 
-    // Build JSON schema entries (serde_json) and Document schema entries (Bedrock)
-    let json_props_entries = field_metas.iter().map(|fm| {
-        let name = &fm.name;
-        let typ = &fm.json_type;
-        let desc_tokens = fm
-            .description
-            .as_ref()
-            .map(|d| quote! { Some(#d) })
-            .unwrap_or_else(|| quote! { None::<&str> });
-        let items_clause = if let Some(items_t) = &fm.items_type {
-            quote! {
-                map.insert(
-                    "items".to_string(),
-                    ::derive_agent_tools::__macro_support::serde_json::json!({ "type": #items_t })
-                );
+            fn tool_description() -> Option<&'static str> {
+                Self::__AGENT_TOOL_DESCRIPTION
             }
-        } else {
-            quote! {}
-        };
 
-        quote! {
-            let mut map = ::derive_agent_tools::__macro_support::serde_json::Map::<String, ::derive_agent_tools::__macro_support::serde_json::Value>::new();
-            map.insert("type".to_string(), ::derive_agent_tools::__macro_support::serde_json::Value::String(#typ.to_string()));
-            if let Some(desc) = #desc_tokens { map.insert("description".to_string(), ::derive_agent_tools::__macro_support::serde_json::Value::String(desc.to_string())); }
-            #items_clause
-            props.insert(#name.to_string(), ::derive_agent_tools::__macro_support::serde_json::Value::Object(map));
-        }
-    });
-
-    let doc_props_entries = field_metas.iter().map(|fm| {
-        let name = &fm.name;
-        let typ = &fm.json_type;
-        let desc_tokens = fm
-            .description
-            .as_ref()
-            .map(|d| quote! { Some(#d) })
-            .unwrap_or_else(|| quote! { None::<&str> });
-        let items_clause = if let Some(items_t) = &fm.items_type {
-            quote! {
-                let mut items_map = ::std::collections::HashMap::<::std::string::String, ::derive_agent_tools::__macro_support::aws_smithy_types::Document>::new();
-                items_map.insert(
-                    "type".to_string(),
-                    ::derive_agent_tools::__macro_support::aws_smithy_types::Document::String(#items_t.to_string())
-                );
-                map.insert(
-                    "items".to_string(),
-                    ::derive_agent_tools::__macro_support::aws_smithy_types::Document::Object(items_map)
-                );
+            fn tool_title() -> Option<&'static str> {
+                #title_tokens
             }
-        } else {
-            quote! {}
-        };
-        quote! {
-            let mut map = ::std::collections::HashMap::<::std::string::String, ::derive_agent_tools::__macro_support::aws_smithy_types::Document>::new();
-            map.insert("type".to_string(), ::derive_agent_tools::__macro_support::aws_smithy_types::Document::String(#typ.to_string()));
-            if let Some(desc) = #desc_tokens { map.insert("description".to_string(), ::derive_agent_tools::__macro_support::aws_smithy_types::Document::String(desc.to_string())); }
-            #items_clause
-            props.insert(#name.to_string(), ::derive_agent_tools::__macro_support::aws_smithy_types::Document::Object(map));
-        }
-    });
 
-    let required_fields: Vec<syn::LitStr> = field_metas
-        .iter()
-        .filter(|fm| fm.required)
-        .map(|fm| syn::LitStr::new(&fm.name, proc_macro2::Span::call_site()))
-        .collect();
+            fn tool_annotations() -> ::derive_agent_tools::ToolAnnotations {
+                ::derive_agent_tools::ToolAnnotations {
+                    read_only: #read_only,
+                    destructive: #destructive,
+                    idempotent: #idempotent,
+                    open_world: #open_world,
+                }
+            }
 
-    let json_required_section = if required_fields.is_empty() {
-        quote! {}
-    } else {
-        quote! {
-            let required = vec![ #( #required_fields.to_string() ),* ];
-            schema.insert(
-                "required".to_string(),
-                ::derive_agent_tools::__macro_support::serde_json::json!(required)
-            );
+            fn tool_schema() -> ::derive_agent_tools::Schema {
+                Self::__AGENT_TOOL_SCHEMA.build(None)
+            }
         }
     };
 
-    let doc_required_section = if required_fields.is_empty() {
-        quote! {}
-    } else {
-        quote! {
-            let required: ::std::vec::Vec<_> = vec![
-                #( ::derive_agent_tools::__macro_support::aws_smithy_types::Document::String(#required_fields.to_string()) ),*
-            ];
-            schema.insert(
-                "required".to_string(),
-                ::derive_agent_tools::__macro_support::aws_smithy_types::Document::Array(required)
-            );
+    // Parse error alias and TryFrom<&Document> delegating to the trait
+    let try_from_impl = quote! {
+        #[doc(hidden)]
+        pub type #err_ident = ::derive_agent_tools::ToolParseError;
+
+        #ms::if_bedrock! {
+            impl<'a> ::std::convert::TryFrom<&'a #ms::aws_smithy_types::Document> for #ident {
+                type Error = ::derive_agent_tools::ToolParseError;
+                fn try_from(doc: &'a #ms::aws_smithy_types::Document) -> ::std::result::Result<Self, Self::Error> {
+                    <Self as ::derive_agent_tools::AgentTool>::from_document(doc)
+                }
+            }
         }
     };
 
-    // Implementations
-    let tool_impl = quote! {
-        impl #ident {
-            const __AGENT_TOOL_NAME: &'static str = #computed_tool_name;
-            const __AGENT_TOOL_DESCRIPTION: Option<&'static str> = #description_tokens;
+    let handler_impl =
+        handler.map(|(path, is_async)| handler_tokens(ident, &path, is_async, context));
 
-            /// Returns the logical name of this tool.
-            pub fn tool_name() -> &'static str {
-                Self::__AGENT_TOOL_NAME
-            }
+    Ok(quote! {
+        #tool_impl
+        #try_from_impl
+        #handler_impl
+        #partial_impl
+    })
+}
+
+/// Generates `<Tool>Partial`, with every field optional, and its
+/// `PartialAgentTool` impl.
+///
+/// Each field is deserialized on its own so that one incomplete value does
+/// not hide the others.
+fn partial_tokens(input: &DeriveInput, fields: &[NamedField]) -> proc_macro2::TokenStream {
+    let ident = &input.ident;
+    let vis = &input.vis;
+    let partial_ident = format_ident!("{}Partial", ident);
+
+    let mut declarations = Vec::new();
+    let mut initializers = Vec::new();
+    for NamedField {
+        field,
+        aliases,
+        custom_deserialize,
+        property,
+    } in fields
+    {
+        let Some(field_ident) = &field.ident else {
+            continue;
+        };
+        // The field's own deserializer expects the complete value, and its
+        // type may not implement `Deserialize` at all.
+        if *custom_deserialize {
+            continue;
+        }
+        let ty = schema::option_inner(&field.ty).unwrap_or_else(|| field.ty.clone());
+        let key = &property.name;
+        let field_vis = &field.vis;
+        declarations.push(quote! { #field_vis #field_ident: ::std::option::Option<#ty> });
+        if property.flatten {
+            // Flattened fields are read from the whole input object.
+            initializers.push(quote! {
+                #field_ident: <#ty as ::derive_agent_tools::__macro_support::serde::Deserialize>::deserialize(input).ok()
+            });
+            continue;
+        }
+        initializers.push(quote! {
+            #field_ident: input
+                .get(#key)
+                #( .or_else(|| input.get(#aliases)) )*
+                .and_then(|value| <#ty as ::derive_agent_tools::__macro_support::serde::Deserialize>::deserialize(value).ok())
+        });
+    }
 
-            /// Returns the JSON Schema for this tool's input in serde_json::Value form.
-            #[cfg(feature = "serde-json")]
-            pub fn tool_schema_json() -> ::derive_agent_tools::__macro_support::serde_json::Value {
-                let mut props = ::derive_agent_tools::__macro_support::serde_json::Map::<String, ::derive_agent_tools::__macro_support::serde_json::Value>::new();
-                #( #json_props_entries ; )*
-                let mut schema = ::derive_agent_tools::__macro_support::serde_json::Map::<String, ::derive_agent_tools::__macro_support::serde_json::Value>::new();
-                schema.insert("type".to_string(), ::derive_agent_tools::__macro_support::serde_json::json!("object"));
-                schema.insert("properties".to_string(), ::derive_agent_tools::__macro_support::serde_json::Value::Object(props));
-                #json_required_section
-                ::derive_agent_tools::__macro_support::serde_json::Value::Object(schema)
+    let doc = format!(
+        "Partial view of [`{ident}`] while its input streams in, built by `PartialAgentTool::parse_partial`."
+    );
+    quote! {
+        ::derive_agent_tools::__macro_support::if_serde_json! {
+            #[doc = #doc]
+            #[derive(Default)]
+            #vis struct #partial_ident {
+                #(#declarations,)*
             }
 
-            /// Builds an AWS Bedrock ToolSpecification for this tool based on the schema.
-            #[cfg(feature = "bedrock")]
-            pub fn tool_spec() -> ::derive_agent_tools::__macro_support::aws_sdk_bedrockruntime::types::ToolSpecification {
-                let mut props = ::std::collections::HashMap::<::std::string::String, ::derive_agent_tools::__macro_support::aws_smithy_types::Document>::new();
-                #( #doc_props_entries ; )*
-                let mut schema = ::std::collections::HashMap::<::std::string::String, ::derive_agent_tools::__macro_support::aws_smithy_types::Document>::new();
-                schema.insert(
-                    "type".to_string(),
-                    ::derive_agent_tools::__macro_support::aws_smithy_types::Document::String("object".to_string())
-                );
-                schema.insert(
-                    "properties".to_string(),
-                    ::derive_agent_tools::__macro_support::aws_smithy_types::Document::Object(props)
-                );
-                #doc_required_section
-
-                let input_schema = ::derive_agent_tools::__macro_support::aws_sdk_bedrockruntime::types::ToolInputSchema::Json(
-                    ::derive_agent_tools::__macro_support::aws_smithy_types::Document::Object(schema)
-                );
-
-                ::derive_agent_tools::__macro_support::aws_sdk_bedrockruntime::types::ToolSpecification::builder()
-                    .name(Self::__AGENT_TOOL_NAME)
-                    .set_description(Self::__AGENT_TOOL_DESCRIPTION.map(|s| s.to_string()))
-                    .input_schema(input_schema)
-                    .build()
-                    .expect("valid ToolSpecification")
+            impl ::derive_agent_tools::PartialAgentTool for #ident {
+                type Partial = #partial_ident;
+
+                fn partial_from_json(input: &::derive_agent_tools::__macro_support::serde_json::Value) -> Self::Partial {
+                    #partial_ident {
+                        #(#initializers,)*
+                    }
+                }
             }
         }
+    }
+}
+
+/// Implements the handler traits by forwarding to a `#[tool(handler = ...)]`
+/// function.
+///
+/// The function's return type cannot be named from here, so the output is
+/// erased to `serde_json::Value` and the error to `String`. Without a
+/// `context` type the impl is generic over any context and the function only
+/// receives the tool.
+fn handler_tokens(
+    ident: &syn::Ident,
+    path: &syn::Path,
+    is_async: bool,
+    context: Option<syn::Type>,
+) -> proc_macro2::TokenStream {
+    let (generics, ctx_ty, args) = match &context {
+        Some(ty) => (quote! {}, quote! { #ty }, quote! { self, ctx }),
+        None => (
+            quote! { <__Ctx: ::std::marker::Sync> },
+            quote! { __Ctx },
+            quote! { self },
+        ),
+    };
+    let output = quote! {
+        type Output = ::derive_agent_tools::__macro_support::serde_json::Value;
+        type Error = ::std::string::String;
     };
+    let erase = quote! { ::derive_agent_tools::__macro_support::erase_handler_output };
+    let body = if is_async {
+        quote! {
+            impl #generics ::derive_agent_tools::AgentToolHandler<#ctx_ty> for #ident {
+                #output
 
-    // Implement TryFrom<&Document> using serde conversion path
-    let err_ident = format_ident!("{}AgentToolParseError", ident);
-    let try_from_impl = quote! {
-        #[cfg(all(feature = "bedrock", feature = "serde-json"))]
-        #[derive(Debug, Clone)]
-        pub struct #err_ident(pub ::std::string::String);
-        #[cfg(all(feature = "bedrock", feature = "serde-json"))]
-        impl ::std::fmt::Display for #err_ident {
-            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
-                write!(f, "{}", self.0)
+                #[allow(unused_variables)]
+                async fn call(self, ctx: &#ctx_ty) -> ::std::result::Result<Self::Output, Self::Error> {
+                    #erase(#path(#args).await)
+                }
             }
         }
-        #[cfg(all(feature = "bedrock", feature = "serde-json"))]
-        impl ::std::error::Error for #err_ident {}
-
-        #[cfg(all(feature = "bedrock", feature = "serde-json"))]
-        impl<'a> ::std::convert::TryFrom<&'a ::derive_agent_tools::__macro_support::aws_smithy_types::Document> for #ident {
-            type Error = #err_ident;
-            fn try_from(doc: &'a ::derive_agent_tools::__macro_support::aws_smithy_types::Document) -> ::std::result::Result<Self, Self::Error> {
-                fn doc_to_json(doc: &::derive_agent_tools::__macro_support::aws_smithy_types::Document) -> ::std::result::Result<::derive_agent_tools::__macro_support::serde_json::Value, ::std::string::String> {
-                    use ::derive_agent_tools::__macro_support::aws_smithy_types::Document as D;
-                    match doc {
-                        D::Null => Ok(::derive_agent_tools::__macro_support::serde_json::Value::Null),
-                        D::Bool(b) => Ok(::derive_agent_tools::__macro_support::serde_json::Value::Bool(*b)),
-                        D::String(s) => Ok(::derive_agent_tools::__macro_support::serde_json::Value::String(s.clone())),
-                        D::Number(n) => {
-                            use ::derive_agent_tools::__macro_support::serde_json::Number as JNum;
-                            let jn = match *n {
-                                ::derive_agent_tools::__macro_support::aws_smithy_types::Number::PosInt(u) => JNum::from(u),
-                                ::derive_agent_tools::__macro_support::aws_smithy_types::Number::NegInt(i) => JNum::from(i),
-                                ::derive_agent_tools::__macro_support::aws_smithy_types::Number::Float(f) => JNum::from_f64(f).ok_or_else(|| "invalid f64 value in Document::Number".to_string())?,
-                            };
-                            Ok(::derive_agent_tools::__macro_support::serde_json::Value::Number(jn))
-                        }
-                        D::Array(arr) => {
-                            let mut out = ::std::vec::Vec::with_capacity(arr.len());
-                            for v in arr.iter() { out.push(doc_to_json(v)?); }
-                            Ok(::derive_agent_tools::__macro_support::serde_json::Value::Array(out))
-                        }
-                        D::Object(map) => {
-                            let mut m = ::derive_agent_tools::__macro_support::serde_json::Map::new();
-                            for (k, v) in map.iter() { m.insert(k.clone(), doc_to_json(v)?); }
-                            Ok(::derive_agent_tools::__macro_support::serde_json::Value::Object(m))
-                        }
-                    }
+    } else {
+        quote! {
+            impl #generics ::derive_agent_tools::SyncAgentToolHandler<#ctx_ty> for #ident {
+                #output
+
+                #[allow(unused_variables)]
+                fn call_sync(self, ctx: &#ctx_ty) -> ::std::result::Result<Self::Output, Self::Error> {
+                    #erase(#path(#args))
                 }
-                let json = doc_to_json(doc).map_err(#err_ident)?;
-                let obj: Self = ::derive_agent_tools::__macro_support::serde_json::from_value(json).map_err(|e| #err_ident(e.to_string()))?;
-                Ok(obj)
             }
         }
     };
-
-    Ok(quote! {
-        #tool_impl
-        #try_from_impl
-    })
+    quote! {
+        ::derive_agent_tools::__macro_support::handler_impl! {
+            #body
+        }
+    }
 }
 
 fn impl_agent_tool_parameter(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
     let ident = &input.ident;
+
+    let mut description: Option<String> = None;
+    let mut docs = DocStyle::Markdown;
+    parse_tool_attrs(&input.attrs, |meta| {
+        if meta.path.is_ident("description") {
+            let lit: LitStr = meta.value()?.parse()?;
+            description = Some(lit.value());
+            Ok(())
+        } else if meta.path.is_ident("strip_markdown") {
+            if parse_flag(&meta)? {
+                docs = DocStyle::Plain;
+            }
+            Ok(())
+        } else if skip_tool_key(&meta)? {
+            Ok(())
+        } else {
+            Err(unknown_key(&meta, Level::Parameter))
+        }
+    })?;
+    let mut description = description.or_else(|| docs.description(&input.attrs));
+
+    let node = match &input.data {
+        Data::Struct(DataStruct {
+            fields: Fields::Named(fields),
+            ..
+        }) => {
+            let container = SerdeContainer::from_attrs(&input.attrs)?;
+            let fields = parse_named_fields(fields, container.rename_all, container.default, docs)?;
+            SchemaNode::Object(fields.into_iter().map(|f| f.property).collect())
+        }
+        // Newtype structs are transparent, matching serde's representation.
+        Data::Struct(DataStruct {
+            fields: Fields::Unnamed(fields),
+            ..
+        }) if fields.unnamed.len() == 1 => newtype_schema(&fields.unnamed[0])?,
+        Data::Enum(data) => {
+            let (node, variant_list) = enum_schema(input, data, false, docs)?;
+            if let Some(list) = variant_list {
+                description = Some(match description {
+                    Some(desc) => format!("{desc}\n\n{list}"),
+                    None => list,
+                });
+            }
+            node
+        }
+        _ => {
+            return Err(syn::Error::new(
+                input.span(),
+                "AgentToolParameter can only be derived for structs with named fields, newtype structs or enums",
+            ));
+        }
+    };
+
+    let schema_meta = schema::meta_tokens(&node);
+    let description = match description {
+        Some(desc) => quote! { ::std::option::Option::Some(#desc) },
+        None => quote! { ::std::option::Option::None },
+    };
+    let ms = quote! { ::derive_agent_tools::__macro_support };
+
     Ok(quote! {
         impl #ident {
-            #[cfg(feature = "serde-json")]
-            pub fn parameter_schema_json() -> ::derive_agent_tools::__macro_support::serde_json::Value {
-                ::derive_agent_tools::__macro_support::serde_json::json!({ "type": "object" })
+            const __AGENT_TOOL_PARAMETER_SCHEMA: #ms::SchemaMeta = #schema_meta;
+        }
+
+        impl ::derive_agent_tools::AgentToolParameter for #ident {
+            fn parameter_schema() -> ::derive_agent_tools::Schema {
+                Self::__AGENT_TOOL_PARAMETER_SCHEMA.build(#description)
             }
         }
     })
 }
 
-fn infer_json_type(ty: &Type) -> (String, Option<String>) {
-    if let Some(inner) = extract_generic(ty, "Option") {
-        return infer_json_type(&inner);
+struct VariantMeta<'a> {
+    variant: &'a Variant,
+    value: String,
+    description: Option<String>,
+    /// Rename rule for the variant's fields.
+    rename_all: Option<RenameRule>,
+}
+
+/// Collects the deserializable variants of an enum with their wire names,
+/// honoring serde `rename`/`rename_all`, and per-variant descriptions, which
+/// fall back to the variant's doc comment.
+fn parse_variants<'a>(
+    input: &DeriveInput,
+    data: &'a DataEnum,
+    container: &SerdeContainer,
+    derive: &str,
+    docs: DocStyle,
+) -> syn::Result<Vec<VariantMeta<'a>>> {
+    let mut variants = Vec::new();
+    for variant in &data.variants {
+        let serde = SerdeVariant::from_attrs(&variant.attrs)?;
+        if serde.skip || serde.other {
+            continue;
+        }
+        let value = serde.wire_name(&variant.ident, container);
+
+        let mut description = None;
+        parse_tool_attrs(&variant.attrs, |meta| {
+            if meta.path.is_ident("description") {
+                let lit: LitStr = meta.value()?.parse()?;
+                description = Some(lit.value());
+                Ok(())
+            } else {
+                Err(unknown_key(&meta, Level::Variant))
+            }
+        })?;
+        variants.push(VariantMeta {
+            variant,
+            value,
+            description: description.or_else(|| docs.description(&variant.attrs)),
+            rename_all: serde.rename_all.or(container.rename_all_fields),
+        });
+    }
+    if variants.is_empty() {
+        return Err(syn::Error::new(
+            input.span(),
+            format!("{derive} enums need at least one deserializable variant"),
+        ));
     }
-    if let Some(inner) = extract_generic(ty, "Vec") {
-        let (inner_ty, _) = infer_json_type(&inner);
-        return ("array".to_string(), Some(inner_ty));
+    Ok(variants)
+}
+
+/// Builds the schema of an enum following its serde representation.
+///
+/// C-like, externally tagged enums become a string set; the second value is
+/// then a list of variant descriptions for the enum's own description. Any
+/// other enum becomes a `oneOf` (`anyOf` when untagged) with one branch per
+/// variant. `tool` requires every branch to be an object, since a tool input
+/// always is.
+fn enum_schema(
+    input: &DeriveInput,
+    data: &DataEnum,
+    tool: bool,
+    docs: DocStyle,
+) -> syn::Result<(SchemaNode, Option<String>)> {
+    let derive = if tool {
+        "AgentTool"
+    } else {
+        "AgentToolParameter"
+    };
+    let container = SerdeContainer::from_attrs(&input.attrs)?;
+    let variants = parse_variants(input, data, &container, derive, docs)?;
+    let repr = container.enum_repr();
+    let has_unit = variants
+        .iter()
+        .any(|v| matches!(v.variant.fields, Fields::Unit));
+
+    if !tool
+        && matches!(repr, EnumRepr::External)
+        && variants
+            .iter()
+            .all(|v| matches!(v.variant.fields, Fields::Unit))
+    {
+        let list = variants
+            .iter()
+            .filter_map(|v| {
+                let doc = v.description.as_ref()?;
+                Some(format!("- `{}`: {doc}", v.value))
+            })
+            .collect::<Vec<_>>();
+        let list = (!list.is_empty()).then(|| list.join("\n"));
+        let values = variants.into_iter().map(|v| v.value).collect();
+        return Ok((SchemaNode::StringEnum(values), list));
     }
 
-    match ty_to_ident(ty).as_deref() {
-        Some("bool") => ("boolean".to_string(), None),
-        Some("i8") | Some("i16") | Some("i32") | Some("i64") | Some("isize") | Some("u8")
-        | Some("u16") | Some("u32") | Some("u64") | Some("usize") => ("integer".to_string(), None),
-        Some("f32") | Some("f64") => ("number".to_string(), None),
-        Some("String") | Some("&str") => ("string".to_string(), None),
-        _ => ("object".to_string(), None),
+    let mut branches = Vec::new();
+    for v in variants {
+        let payload = match &v.variant.fields {
+            Fields::Unit => None,
+            Fields::Named(fields) => {
+                let fields = parse_named_fields(fields, v.rename_all, false, docs)?;
+                Some(SchemaNode::Object(
+                    fields.into_iter().map(|f| f.property).collect(),
+                ))
+            }
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                Some(newtype_schema(&fields.unnamed[0])?)
+            }
+            Fields::Unnamed(_) => {
+                return Err(syn::Error::new(
+                    v.variant.span(),
+                    format!("{derive} does not support tuple variants with more than one field"),
+                ));
+            }
+        };
+        let tag_property = |tag: &str| Property {
+            name: tag.to_string(),
+            description: None,
+            required: true,
+            schema: SchemaNode::StringEnum(vec![v.value.clone()]),
+            flatten: false,
+        };
+        let schema = match (&repr, payload) {
+            (EnumRepr::External | EnumRepr::Untagged, None) if tool => {
+                return Err(syn::Error::new(
+                    v.variant.span(),
+                    "AgentTool inputs must be objects; unit variants need `#[serde(tag = \"...\")]`",
+                ));
+            }
+            (EnumRepr::External, None) => SchemaNode::StringEnum(vec![v.value.clone()]),
+            (EnumRepr::External, Some(payload)) => SchemaNode::Object(vec![Property {
+                name: v.value.clone(),
+                description: None,
+                required: true,
+                schema: payload,
+                flatten: false,
+            }]),
+            (EnumRepr::Internal { tag } | EnumRepr::Adjacent { tag, .. }, None) => {
+                SchemaNode::Object(vec![tag_property(tag)])
+            }
+            (EnumRepr::Internal { tag }, Some(SchemaNode::Object(mut props))) => {
+                props.insert(0, tag_property(tag));
+                SchemaNode::Object(props)
+            }
+            (
+                EnumRepr::Internal { .. },
+                Some(SchemaNode::Primitive(_) | SchemaNode::Array(_) | SchemaNode::StringEnum(_)),
+            ) => {
+                return Err(syn::Error::new(
+                    v.variant.fields.span(),
+                    "internally tagged newtype variants must hold a struct or map; serde cannot add the tag to a string, number, boolean or array",
+                ));
+            }
+            (EnumRepr::Internal { tag }, Some(payload)) => SchemaNode::Tagged {
+                tag: tag.to_string(),
+                value: v.value.clone(),
+                inner: Box::new(payload),
+            },
+            (EnumRepr::Adjacent { tag, content }, Some(payload)) => SchemaNode::Object(vec![
+                tag_property(tag),
+                Property {
+                    name: content.to_string(),
+                    description: None,
+                    required: true,
+                    schema: payload,
+                    flatten: false,
+                },
+            ]),
+            (EnumRepr::Untagged, None) => SchemaNode::Null,
+            (EnumRepr::Untagged, Some(payload)) => payload,
+        };
+        branches.push(Branch {
+            description: v.description,
+            schema,
+        });
     }
+
+    let object = tool
+        || match repr {
+            EnumRepr::Internal { .. } | EnumRepr::Adjacent { .. } => true,
+            EnumRepr::External => !has_unit,
+            EnumRepr::Untagged => false,
+        };
+    let keyword = if matches!(repr, EnumRepr::Untagged) {
+        "anyOf"
+    } else {
+        "oneOf"
+    };
+    Ok((
+        SchemaNode::Union {
+            keyword,
+            object,
+            branches,
+        },
+        None,
+    ))
 }
 
-fn ty_to_ident(ty: &Type) -> Option<String> {
-    match ty {
-        Type::Path(p) => p.path.segments.last().map(|s| s.ident.to_string()),
-        Type::Reference(r) => ty_to_ident(&r.elem),
-        _ => None,
+/// Reads a boolean flag written either bare (`read_only`) or with a value
+/// (`read_only = false`).
+fn parse_flag(meta: &syn::meta::ParseNestedMeta) -> syn::Result<bool> {
+    if meta.input.peek(syn::Token![=]) {
+        let lit: syn::LitBool = meta.value()?.parse()?;
+        Ok(lit.value)
+    } else {
+        Ok(true)
     }
 }
 
-fn extract_generic(ty: &Type, ident: &str) -> Option<Type> {
-    if let Type::Path(p) = ty {
-        if let Some(seg) = p.path.segments.last() {
-            if seg.ident == ident {
-                if let syn::PathArguments::AngleBracketed(args) = &seg.arguments {
-                    if let Some(syn::GenericArgument::Type(inner)) = args.args.first() {
-                        return Some(inner.clone());
-                    }
-                }
+/// The schema of the single field of a newtype struct or variant, which
+/// only takes `#[tool(schema = ...)]`.
+fn newtype_schema(field: &syn::Field) -> syn::Result<SchemaNode> {
+    let mut custom_schema: Option<syn::Path> = None;
+    parse_tool_attrs(&field.attrs, |meta| {
+        if meta.path.is_ident("schema") {
+            custom_schema = Some(meta.value()?.parse()?);
+            Ok(())
+        } else {
+            Err(unknown_key(&meta, Level::Newtype))
+        }
+    })?;
+    Ok(match custom_schema {
+        Some(path) => SchemaNode::Custom(path),
+        None => schema::infer_schema(&field.ty),
+    })
+}
+
+/// A named field that appears in the schema.
+struct NamedField<'a> {
+    field: &'a syn::Field,
+    /// Other names serde accepts for the field, which are not advertised.
+    aliases: Vec<String>,
+    /// Whether serde reads the field with `deserialize_with` or `with`.
+    custom_deserialize: bool,
+    property: Property,
+}
+
+/// Collects per-field metadata (type, description, required) for a struct
+/// with named fields, following the serde attributes that change the wire
+/// shape: fields are renamed like serde does, skipped fields are left out,
+/// fields are required unless serde accepts them missing (`Option` or
+/// defaulted) or they are marked `#[tool(optional)]`, and flattened fields
+/// contribute the nested type's properties.
+fn parse_named_fields(
+    fields: &FieldsNamed,
+    rename_all: Option<RenameRule>,
+    container_default: bool,
+    docs: DocStyle,
+) -> syn::Result<Vec<NamedField<'_>>> {
+    let mut named = Vec::new();
+    for field in fields.named.iter() {
+        let Some(field_ident) = &field.ident else {
+            continue;
+        };
+
+        let mut required = None;
+        let mut optional = None;
+        let mut description: Option<String> = None;
+        let mut custom_schema: Option<syn::Path> = None;
+        parse_tool_attrs(&field.attrs, |meta| {
+            if meta.path.is_ident("required") {
+                required = Some(meta.path.span());
+                Ok(())
+            } else if meta.path.is_ident("optional") {
+                optional = Some(meta.path.span());
+                Ok(())
+            } else if meta.path.is_ident("description") {
+                let lit: LitStr = meta.value()?.parse()?;
+                description = Some(lit.value());
+                Ok(())
+            } else if meta.path.is_ident("schema") {
+                custom_schema = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(unknown_key(&meta, Level::Field))
             }
+        })?;
+
+        let serde = SerdeField::from_attrs(&field.attrs)?;
+        if serde.skip {
+            continue;
+        }
+        let inner = schema::option_inner(&field.ty);
+        let defaulted = serde.default.is_some() || container_default;
+        if serde.flatten.is_some() {
+            if let Some(span) = required
+                .or(optional)
+                .or(description.as_ref().map(|_| field_ident.span()))
+                .or(custom_schema.as_ref().map(|path| path.span()))
+            {
+                return Err(syn::Error::new(
+                    span,
+                    "`#[serde(flatten)]` fields take `required`, `optional`, `description` and `schema` from the flattened type",
+                ));
+            }
+            named.push(NamedField {
+                field,
+                property: Property {
+                    name: serde.wire_name(field_ident, rename_all),
+                    description: None,
+                    required: inner.is_none() && !defaulted,
+                    schema: SchemaNode::External(inner.unwrap_or_else(|| field.ty.clone())),
+                    flatten: true,
+                },
+                aliases: Vec::new(),
+                custom_deserialize: serde.deserialize_with,
+            });
+            continue;
         }
+        // serde fills in a missing `Option` field unless a custom
+        // deserializer takes over.
+        let accepts_missing = defaulted || (inner.is_some() && !serde.deserialize_with);
+        if let Some(span) = required {
+            if optional.is_some() {
+                return Err(syn::Error::new(
+                    span,
+                    "a field cannot be both `required` and `optional`",
+                ));
+            }
+            if defaulted {
+                return Err(syn::Error::new(
+                    span,
+                    "`required` conflicts with `#[serde(default)]`, which accepts the field missing",
+                ));
+            }
+            if accepts_missing {
+                return Err(syn::Error::new(
+                    span,
+                    "`required` on an `Option` field: a missing value deserializes as `None`; use a non-`Option` type or `#[serde(deserialize_with = \"...\")]`",
+                ));
+            }
+        }
+
+        named.push(NamedField {
+            field,
+            property: Property {
+                name: serde.wire_name(field_ident, rename_all),
+                description: description.or_else(|| docs.description(&field.attrs)),
+                required: optional.is_none() && !accepts_missing,
+                schema: match custom_schema {
+                    Some(path) => SchemaNode::Custom(path),
+                    None => schema::infer_schema(&field.ty),
+                },
+                flatten: false,
+            },
+            aliases: serde.aliases,
+            custom_deserialize: serde.deserialize_with,
+        });
     }
-    None
+    Ok(named)
 }
@@ -0,0 +1,182 @@
+//! Compile-time schema model shared by both derives.
+//!
+//! Field metadata is collected into a [`SchemaNode`] tree, then emitted as a
+//! constant `SchemaMeta` that the facade crate builds into a `Schema` at runtime.
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::Type;
+
+pub(crate) enum SchemaNode {
+    /// A primitive type, named by its `SchemaType` variant such as `String`.
+    Primitive(&'static str),
+    /// `{"type": "array", "items": ...}`
+    Array(Box<SchemaNode>),
+    /// `{"type": "object", "properties": ..., "required": [...]}`
+    Object(Vec<Property>),
+    /// `{"type": "string", "enum": [...]}`
+    StringEnum(Vec<String>),
+    /// `{"type": "null"}`
+    Null,
+    /// `{"oneOf": [...]}` or `{"anyOf": [...]}`, additionally typed as an
+    /// object when every branch is known to be one.
+    Union {
+        keyword: &'static str,
+        object: bool,
+        branches: Vec<Branch>,
+    },
+    /// `inner`'s object schema with a required string tag property merged in,
+    /// for newtype variants of internally tagged enums.
+    Tagged {
+        tag: String,
+        value: String,
+        inner: Box<SchemaNode>,
+    },
+    /// A type implementing `AgentToolParameter`, whose schema is embedded.
+    External(Type),
+    /// `#[tool(schema = path)]`: a `fn() -> Schema` supplying the schema.
+    Custom(syn::Path),
+}
+
+pub(crate) struct Property {
+    pub(crate) name: String,
+    pub(crate) description: Option<String>,
+    pub(crate) required: bool,
+    pub(crate) schema: SchemaNode,
+    /// `#[serde(flatten)]`: `schema` is the nested type, whose properties are
+    /// merged in, and `required` is false when the field is an `Option`.
+    pub(crate) flatten: bool,
+}
+
+pub(crate) struct Branch {
+    pub(crate) description: Option<String>,
+    pub(crate) schema: SchemaNode,
+}
+
+/// Maps a Rust field type to a schema node. `Option<T>` is unwrapped; the
+/// caller decides requiredness.
+pub(crate) fn infer_schema(ty: &Type) -> SchemaNode {
+    if let Some(inner) = extract_generic(ty, "Option") {
+        return infer_schema(&inner);
+    }
+    if let Some(inner) = extract_generic(ty, "Vec") {
+        return SchemaNode::Array(Box::new(infer_schema(&inner)));
+    }
+
+    match ty_to_ident(ty).as_deref() {
+        Some("bool") => SchemaNode::Primitive("Boolean"),
+        Some("i8") | Some("i16") | Some("i32") | Some("i64") | Some("isize") | Some("u8")
+        | Some("u16") | Some("u32") | Some("u64") | Some("usize") => {
+            SchemaNode::Primitive("Integer")
+        }
+        Some("f32") | Some("f64") => SchemaNode::Primitive("Number"),
+        Some("String") | Some("str") | Some("char") => SchemaNode::Primitive("String"),
+        _ => SchemaNode::External(ty.clone()),
+    }
+}
+
+fn ty_to_ident(ty: &Type) -> Option<String> {
+    match ty {
+        Type::Path(p) => p.path.segments.last().map(|s| s.ident.to_string()),
+        Type::Reference(r) => ty_to_ident(&r.elem),
+        _ => None,
+    }
+}
+
+/// Returns `T` for an `Option<T>` field type.
+pub(crate) fn option_inner(ty: &Type) -> Option<Type> {
+    extract_generic(ty, "Option")
+}
+
+fn extract_generic(ty: &Type, ident: &str) -> Option<Type> {
+    if let Type::Path(p) = ty
+        && let Some(seg) = p.path.segments.last()
+        && seg.ident == ident
+        && let syn::PathArguments::AngleBracketed(args) = &seg.arguments
+        && let Some(syn::GenericArgument::Type(inner)) = args.args.first()
+    {
+        return Some(inner.clone());
+    }
+    None
+}
+
+// Metadata emission ---------------------------------------------------------
+
+/// Emits a constant expression building the facade's `SchemaMeta` for
+/// `node`, which the facade builds into a `Schema` at runtime.
+pub(crate) fn meta_tokens(node: &SchemaNode) -> TokenStream {
+    let ms = quote! { ::derive_agent_tools::__macro_support };
+    let opt_str = |s: &Option<String>| match s {
+        Some(s) => quote! { ::std::option::Option::Some(#s) },
+        None => quote! { ::std::option::Option::None },
+    };
+    match node {
+        SchemaNode::Primitive(variant) => {
+            let variant = format_ident!("{}", variant);
+            quote! { #ms::SchemaMeta::Primitive(#ms::SchemaType::#variant) }
+        }
+        SchemaNode::Array(items) => {
+            let items = meta_tokens(items);
+            quote! { #ms::SchemaMeta::Array(&#items) }
+        }
+        SchemaNode::Object(props) => {
+            let props = props.iter().map(|p| {
+                let required = p.required;
+                if p.flatten
+                    && let SchemaNode::External(ty) = &p.schema
+                {
+                    let optional = !required;
+                    return quote! {
+                        #ms::PropertyMeta::Flatten {
+                            schema: <#ty as ::derive_agent_tools::AgentToolParameter>::parameter_schema,
+                            optional: #optional,
+                        }
+                    };
+                }
+                let name = &p.name;
+                let description = opt_str(&p.description);
+                let schema = meta_tokens(&p.schema);
+                quote! {
+                    #ms::PropertyMeta::Field {
+                        name: #name,
+                        description: #description,
+                        required: #required,
+                        schema: #schema,
+                    }
+                }
+            });
+            quote! { #ms::SchemaMeta::Object(&[ #( #props ),* ]) }
+        }
+        SchemaNode::StringEnum(values) => {
+            quote! { #ms::SchemaMeta::StringEnum(&[ #( #values ),* ]) }
+        }
+        SchemaNode::Null => quote! { #ms::SchemaMeta::Null },
+        SchemaNode::Union {
+            keyword,
+            object,
+            branches,
+        } => {
+            let branches = branches.iter().map(|b| {
+                let description = opt_str(&b.description);
+                let schema = meta_tokens(&b.schema);
+                quote! { #ms::BranchMeta { description: #description, schema: #schema } }
+            });
+            let any_of = *keyword == "anyOf";
+            quote! {
+                #ms::SchemaMeta::Union {
+                    any_of: #any_of,
+                    object: #object,
+                    branches: &[ #( #branches ),* ],
+                }
+            }
+        }
+        SchemaNode::Tagged { tag, value, inner } => {
+            let inner = meta_tokens(inner);
+            quote! { #ms::SchemaMeta::Tagged { tag: #tag, value: #value, inner: &#inner } }
+        }
+        SchemaNode::External(ty) => quote! {
+            #ms::SchemaMeta::External(<#ty as ::derive_agent_tools::AgentToolParameter>::parameter_schema)
+        },
+        SchemaNode::Custom(path) => quote! { #ms::SchemaMeta::External(#path) },
+    }
+}
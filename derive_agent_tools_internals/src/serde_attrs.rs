@@ -0,0 +1,329 @@
+//! Reading the `#[serde(...)]` attributes that affect the wire shape.
+//!
+//! Only the deserialize side matters for tool inputs, so
+//! `rename(deserialize = "...")` is honored and `serialize = "..."` ignored.
+//! Keys that do not affect the schema are skipped.
+
+use proc_macro2::Span;
+use syn::ext::IdentExt;
+use syn::meta::ParseNestedMeta;
+use syn::spanned::Spanned;
+use syn::{Attribute, LitStr, Token};
+
+/// serde's `rename_all` rules.
+#[derive(Clone, Copy)]
+pub(crate) enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    fn parse(lit: &LitStr) -> syn::Result<Self> {
+        Ok(match lit.value().as_str() {
+            "lowercase" => Self::Lower,
+            "UPPERCASE" => Self::Upper,
+            "PascalCase" => Self::Pascal,
+            "camelCase" => Self::Camel,
+            "snake_case" => Self::Snake,
+            "SCREAMING_SNAKE_CASE" => Self::ScreamingSnake,
+            "kebab-case" => Self::Kebab,
+            "SCREAMING-KEBAB-CASE" => Self::ScreamingKebab,
+            other => {
+                return Err(syn::Error::new(
+                    lit.span(),
+                    format!("unknown serde rename rule `{other}`"),
+                ));
+            }
+        })
+    }
+
+    /// Applies the rule to a `PascalCase` variant name.
+    pub(crate) fn apply_to_variant(self, variant: &str) -> String {
+        match self {
+            Self::Pascal => variant.to_string(),
+            Self::Lower => variant.to_ascii_lowercase(),
+            Self::Upper => variant.to_ascii_uppercase(),
+            Self::Camel => {
+                let mut chars = variant.chars();
+                match chars.next() {
+                    Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+                    None => String::new(),
+                }
+            }
+            Self::Snake => {
+                let mut snake = String::new();
+                for (i, ch) in variant.char_indices() {
+                    if i > 0 && ch.is_uppercase() {
+                        snake.push('_');
+                    }
+                    snake.push(ch.to_ascii_lowercase());
+                }
+                snake
+            }
+            Self::ScreamingSnake => Self::Snake.apply_to_variant(variant).to_ascii_uppercase(),
+            Self::Kebab => Self::Snake.apply_to_variant(variant).replace('_', "-"),
+            Self::ScreamingKebab => Self::ScreamingSnake
+                .apply_to_variant(variant)
+                .replace('_', "-"),
+        }
+    }
+
+    /// Applies the rule to a `snake_case` field name.
+    pub(crate) fn apply_to_field(self, field: &str) -> String {
+        match self {
+            Self::Lower | Self::Snake => field.to_string(),
+            Self::Upper | Self::ScreamingSnake => field.to_ascii_uppercase(),
+            Self::Pascal => {
+                let mut pascal = String::new();
+                let mut capitalize = true;
+                for ch in field.chars() {
+                    if ch == '_' {
+                        capitalize = true;
+                    } else if capitalize {
+                        pascal.push(ch.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        pascal.push(ch);
+                    }
+                }
+                pascal
+            }
+            Self::Camel => {
+                let pascal = Self::Pascal.apply_to_field(field);
+                let mut chars = pascal.chars();
+                match chars.next() {
+                    Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+                    None => String::new(),
+                }
+            }
+            Self::Kebab => field.replace('_', "-"),
+            Self::ScreamingKebab => field.to_ascii_uppercase().replace('_', "-"),
+        }
+    }
+}
+
+/// Container-level `#[serde(...)]` attributes.
+#[derive(Default)]
+pub(crate) struct SerdeContainer {
+    pub(crate) rename_all: Option<RenameRule>,
+    /// `rename_all_fields`: applies to the fields of every enum variant.
+    pub(crate) rename_all_fields: Option<RenameRule>,
+    /// `default`: every missing field is filled from `Default`.
+    pub(crate) default: bool,
+    /// `tag = "..."`: internally tagged, or adjacently tagged with `content`.
+    pub(crate) tag: Option<String>,
+    pub(crate) content: Option<String>,
+    pub(crate) untagged: bool,
+}
+
+/// How serde represents an enum on the wire.
+pub(crate) enum EnumRepr<'a> {
+    External,
+    Internal { tag: &'a str },
+    Adjacent { tag: &'a str, content: &'a str },
+    Untagged,
+}
+
+impl SerdeContainer {
+    pub(crate) fn from_attrs(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut container = Self::default();
+        for attr in attrs {
+            if !attr.path().is_ident("serde") {
+                continue;
+            }
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename_all") {
+                    if let Some(lit) = deserialize_name(&meta)? {
+                        container.rename_all = Some(RenameRule::parse(&lit)?);
+                    }
+                    Ok(())
+                } else if meta.path.is_ident("rename_all_fields") {
+                    if let Some(lit) = deserialize_name(&meta)? {
+                        container.rename_all_fields = Some(RenameRule::parse(&lit)?);
+                    }
+                    Ok(())
+                } else if meta.path.is_ident("default") {
+                    container.default = true;
+                    skip_meta(&meta)
+                } else if meta.path.is_ident("tag") {
+                    container.tag = Some(meta.value()?.parse::<LitStr>()?.value());
+                    Ok(())
+                } else if meta.path.is_ident("content") {
+                    container.content = Some(meta.value()?.parse::<LitStr>()?.value());
+                    Ok(())
+                } else if meta.path.is_ident("untagged") {
+                    container.untagged = true;
+                    Ok(())
+                } else {
+                    skip_meta(&meta)
+                }
+            })?;
+        }
+        Ok(container)
+    }
+
+    pub(crate) fn enum_repr(&self) -> EnumRepr<'_> {
+        match (&self.tag, &self.content, self.untagged) {
+            (_, _, true) => EnumRepr::Untagged,
+            (Some(tag), Some(content), _) => EnumRepr::Adjacent { tag, content },
+            (Some(tag), None, _) => EnumRepr::Internal { tag },
+            (None, _, _) => EnumRepr::External,
+        }
+    }
+}
+
+/// Variant-level `#[serde(...)]` attributes.
+#[derive(Default)]
+pub(crate) struct SerdeVariant {
+    pub(crate) rename: Option<String>,
+    /// `rename_all`: applies to the variant's fields.
+    pub(crate) rename_all: Option<RenameRule>,
+    /// `skip` / `skip_deserializing`: the variant is never accepted.
+    pub(crate) skip: bool,
+    /// `other`: catch-all for unknown values, not advertised.
+    pub(crate) other: bool,
+}
+
+impl SerdeVariant {
+    pub(crate) fn from_attrs(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut variant = Self::default();
+        for attr in attrs {
+            if !attr.path().is_ident("serde") {
+                continue;
+            }
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    if let Some(lit) = deserialize_name(&meta)? {
+                        variant.rename = Some(lit.value());
+                    }
+                    Ok(())
+                } else if meta.path.is_ident("rename_all") {
+                    if let Some(lit) = deserialize_name(&meta)? {
+                        variant.rename_all = Some(RenameRule::parse(&lit)?);
+                    }
+                    Ok(())
+                } else if meta.path.is_ident("skip") || meta.path.is_ident("skip_deserializing") {
+                    variant.skip = true;
+                    Ok(())
+                } else if meta.path.is_ident("other") {
+                    variant.other = true;
+                    Ok(())
+                } else {
+                    skip_meta(&meta)
+                }
+            })?;
+        }
+        Ok(variant)
+    }
+
+    /// The name serde expects on the wire for a variant.
+    pub(crate) fn wire_name(&self, ident: &syn::Ident, container: &SerdeContainer) -> String {
+        if let Some(rename) = &self.rename {
+            return rename.clone();
+        }
+        let name = ident.to_string();
+        match container.rename_all {
+            Some(rule) => rule.apply_to_variant(&name),
+            None => name,
+        }
+    }
+}
+
+/// Field-level `#[serde(...)]` attributes.
+#[derive(Default)]
+pub(crate) struct SerdeField {
+    pub(crate) rename: Option<String>,
+    /// Extra names accepted when deserializing; not advertised.
+    pub(crate) aliases: Vec<String>,
+    /// `skip` / `skip_deserializing`: the field is never read.
+    pub(crate) skip: bool,
+    /// `default` / `default = "..."`: a missing field is filled in.
+    pub(crate) default: Option<Span>,
+    /// `flatten`: the field's own fields appear inline.
+    pub(crate) flatten: Option<Span>,
+    /// `deserialize_with` / `with`: serde no longer fills a missing `Option`
+    /// field with `None`.
+    pub(crate) deserialize_with: bool,
+}
+
+impl SerdeField {
+    pub(crate) fn from_attrs(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut field = Self::default();
+        for attr in attrs {
+            if !attr.path().is_ident("serde") {
+                continue;
+            }
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    if let Some(lit) = deserialize_name(&meta)? {
+                        field.rename = Some(lit.value());
+                    }
+                    Ok(())
+                } else if meta.path.is_ident("alias") {
+                    field.aliases.push(meta.value()?.parse::<LitStr>()?.value());
+                    Ok(())
+                } else if meta.path.is_ident("skip") || meta.path.is_ident("skip_deserializing") {
+                    field.skip = true;
+                    Ok(())
+                } else if meta.path.is_ident("default") {
+                    field.default = Some(meta.path.span());
+                    skip_meta(&meta)
+                } else if meta.path.is_ident("flatten") {
+                    field.flatten = Some(meta.path.span());
+                    Ok(())
+                } else if meta.path.is_ident("deserialize_with") || meta.path.is_ident("with") {
+                    field.deserialize_with = true;
+                    skip_meta(&meta)
+                } else {
+                    skip_meta(&meta)
+                }
+            })?;
+        }
+        Ok(field)
+    }
+
+    /// The name serde expects on the wire for a field.
+    pub(crate) fn wire_name(&self, ident: &syn::Ident, rename_all: Option<RenameRule>) -> String {
+        if let Some(rename) = &self.rename {
+            return rename.clone();
+        }
+        let name = ident.unraw().to_string();
+        match rename_all {
+            Some(rule) => rule.apply_to_field(&name),
+            None => name,
+        }
+    }
+}
+
+/// Reads `key = "..."` or `key(deserialize = "...")`, returning the name used
+/// when deserializing.
+fn deserialize_name(meta: &ParseNestedMeta) -> syn::Result<Option<LitStr>> {
+    if meta.input.peek(Token![=]) {
+        return Ok(Some(meta.value()?.parse()?));
+    }
+    let mut name = None;
+    meta.parse_nested_meta(|inner| {
+        let lit: LitStr = inner.value()?.parse()?;
+        if inner.path.is_ident("deserialize") {
+            name = Some(lit);
+        }
+        Ok(())
+    })?;
+    Ok(name)
+}
+
+/// Consumes a serde key this crate does not interpret.
+fn skip_meta(meta: &ParseNestedMeta) -> syn::Result<()> {
+    if meta.input.peek(Token![=]) {
+        meta.value()?.parse::<syn::Expr>()?;
+    } else if meta.input.peek(syn::token::Paren) {
+        meta.parse_nested_meta(|inner| skip_meta(&inner))?;
+    }
+    Ok(())
+}
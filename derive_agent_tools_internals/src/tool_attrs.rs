@@ -0,0 +1,157 @@
+//! Validation of the `#[tool(...)]` keys.
+//!
+//! Each level (the type of an `AgentTool` or `AgentToolParameter`, a field, a
+//! variant) accepts its own keys. Anything else is a compile error, so a typo
+//! never silently drops part of a schema.
+
+use quote::ToTokens;
+use syn::Attribute;
+use syn::meta::ParseNestedMeta;
+
+/// Where a `#[tool(...)]` attribute is written.
+#[derive(Clone, Copy)]
+pub(crate) enum Level {
+    /// The type deriving `AgentTool`.
+    Tool,
+    /// The type deriving `AgentToolParameter`.
+    Parameter,
+    Field,
+    /// The single field of a newtype struct or variant.
+    Newtype,
+    Variant,
+}
+
+impl Level {
+    fn keys(self) -> &'static [&'static str] {
+        match self {
+            Self::Tool => &[
+                "name",
+                "description",
+                "title",
+                "read_only",
+                "destructive",
+                "idempotent",
+                "open_world",
+                "handler",
+                "async_handler",
+                "context",
+                "strip_markdown",
+            ],
+            Self::Parameter => &["description", "strip_markdown"],
+            Self::Field => &["required", "optional", "description", "schema"],
+            Self::Newtype => &["schema"],
+            Self::Variant => &["description"],
+        }
+    }
+}
+
+/// Runs `f` on every key of the `#[tool(...)]` attributes in `attrs`,
+/// rejecting keys given more than once.
+pub(crate) fn parse_tool_attrs(
+    attrs: &[Attribute],
+    mut f: impl FnMut(ParseNestedMeta) -> syn::Result<()>,
+) -> syn::Result<()> {
+    let mut seen = Vec::new();
+    for attr in attrs {
+        if !attr.path().is_ident("tool") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            let key = key(&meta);
+            if seen.contains(&key) {
+                return Err(meta.error(format!("duplicate `{key}` in `#[tool(...)]`")));
+            }
+            seen.push(key);
+            f(meta)
+        })?;
+    }
+    Ok(())
+}
+
+/// Consumes `meta` if it is a key of the `AgentTool` type. A type deriving
+/// both `AgentTool` and `AgentToolParameter` has them on the same attribute,
+/// so the parameter derive passes over them.
+pub(crate) fn skip_tool_key(meta: &ParseNestedMeta) -> syn::Result<bool> {
+    if !Level::Tool.keys().contains(&key(meta).as_str()) {
+        return Ok(false);
+    }
+    if meta.input.peek(syn::Token![=]) {
+        meta.value()?.parse::<syn::Expr>()?;
+    }
+    Ok(true)
+}
+
+/// The error for a key `level` does not accept: where the key belongs if it
+/// is valid elsewhere, otherwise the closest valid key.
+pub(crate) fn unknown_key(meta: &ParseNestedMeta, level: Level) -> syn::Error {
+    let key = key(meta);
+    let valid_on = |other: Level| other.keys().contains(&key.as_str());
+    let message = match level {
+        Level::Tool | Level::Parameter if valid_on(Level::Field) => {
+            format!("`{key}` goes on a field, not on the type")
+        }
+        Level::Field | Level::Newtype if valid_on(Level::Tool) => {
+            format!("`{key}` goes on the type, not on a field")
+        }
+        Level::Newtype if valid_on(Level::Field) => {
+            format!("`{key}` is not supported on a newtype field; put it on the type or variant")
+        }
+        Level::Variant if valid_on(Level::Field) => {
+            format!("`{key}` goes on the variant's fields, not on the variant")
+        }
+        Level::Variant if valid_on(Level::Tool) => {
+            format!("`{key}` goes on the enum, not on a variant")
+        }
+        _ => match suggestion(&key, level.keys()) {
+            Some(candidate) => {
+                format!("unknown `#[tool(...)]` key `{key}`; did you mean `{candidate}`?")
+            }
+            None => format!(
+                "unknown `#[tool(...)]` key `{key}`; expected one of {}",
+                level
+                    .keys()
+                    .iter()
+                    .map(|k| format!("`{k}`"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        },
+    };
+    meta.error(message)
+}
+
+fn key(meta: &ParseNestedMeta) -> String {
+    match meta.path.get_ident() {
+        Some(ident) => ident.to_string(),
+        None => meta.path.to_token_stream().to_string().replace(' ', ""),
+    }
+}
+
+/// The candidate closest to `key`, if any is close enough to be a typo or
+/// an abbreviation.
+fn suggestion<'a>(key: &str, candidates: &[&'a str]) -> Option<&'a str> {
+    candidates
+        .iter()
+        .map(|candidate| (edit_distance(key, candidate), *candidate))
+        .filter(|(distance, candidate)| {
+            *distance <= 2 || (key.len() >= 3 && candidate.starts_with(key))
+        })
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Levenshtein distance.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}